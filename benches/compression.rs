@@ -0,0 +1,134 @@
+//! Compare plain and zstd-compressed block data.
+//!
+//! Run with `cargo bench --features zstd`. Before timing, the encoded size of
+//! each transaction mix is printed for both coders.
+
+#![allow(missing_docs)]
+
+use alloy::consensus::{SignableTransaction, TxEip1559};
+use alloy::primitives::{keccak256, Address, Bytes, TxKind, U256};
+use alloy::signers::{local::PrivateKeySigner, SignerSync};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use zenith_types::{decode_txns, encode_txns, Alloy2718Coder, ZenithTransaction, ZstdCoder};
+
+const CHAIN_ID: u64 = 17;
+const TXNS_PER_BLOCK: usize = 500;
+
+/// Deterministic pseudo-random bytes.
+fn noise(seed: u64, len: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity(len + 32);
+    let mut i = 0u64;
+    while out.len() < len {
+        out.extend_from_slice(keccak256([seed.to_be_bytes(), i.to_be_bytes()].concat()).as_slice());
+        i += 1;
+    }
+    out.truncate(len);
+    out
+}
+
+fn word(value: &[u8]) -> [u8; 32] {
+    let mut w = [0u8; 32];
+    w[32 - value.len()..].copy_from_slice(value);
+    w
+}
+
+fn eth_transfer(_: u64) -> (Address, Bytes, U256) {
+    (Address::repeat_byte(0x11), Bytes::new(), U256::from(10u64.pow(17)))
+}
+
+fn erc20_transfer(seed: u64) -> (Address, Bytes, U256) {
+    let mut input = vec![0xa9, 0x05, 0x9c, 0xbb];
+    input.extend(word(&noise(seed, 20)));
+    input.extend(word(&noise(seed + 1, 8)));
+    (Address::repeat_byte(0x22), input.into(), U256::ZERO)
+}
+
+fn swap(seed: u64) -> (Address, Bytes, U256) {
+    // a router call with a mix of amounts, addresses, and offsets
+    let mut input = vec![0x38, 0xed, 0x17, 0x39];
+    input.extend(word(&noise(seed, 12)));
+    input.extend(word(&noise(seed + 1, 12)));
+    input.extend(word(&[0xa0]));
+    input.extend(word(&noise(seed + 2, 20)));
+    input.extend(word(&noise(seed + 3, 4)));
+    input.extend(word(&[3]));
+    for i in 0..3 {
+        input.extend(word(&noise(seed + 4 + i, 20)));
+    }
+    (Address::repeat_byte(0x33), input.into(), U256::ZERO)
+}
+
+fn random_calldata(seed: u64) -> (Address, Bytes, U256) {
+    (Address::repeat_byte(0x44), noise(seed, 1024).into(), U256::ZERO)
+}
+
+type Shape = fn(u64) -> (Address, Bytes, U256);
+
+fn block(shapes: &[Shape]) -> Vec<ZenithTransaction> {
+    let signers: Vec<_> =
+        (1u8..=32).map(|i| PrivateKeySigner::from_slice(&keccak256([i])[..]).unwrap()).collect();
+
+    (0..TXNS_PER_BLOCK)
+        .map(|i| {
+            let (to, input, value) = shapes[i % shapes.len()](i as u64);
+            let tx = TxEip1559 {
+                chain_id: CHAIN_ID,
+                nonce: (i / signers.len()) as u64,
+                gas_limit: 21_000 + 16 * input.len() as u64,
+                max_fee_per_gas: 30_000_000_000,
+                max_priority_fee_per_gas: 1_000_000_000,
+                to: TxKind::Call(to),
+                value,
+                access_list: Default::default(),
+                input,
+            };
+            let sig = signers[i % signers.len()].sign_hash_sync(&tx.signature_hash()).unwrap();
+            tx.into_signed(sig).into()
+        })
+        .collect()
+}
+
+fn mixes() -> Vec<(&'static str, Vec<ZenithTransaction>)> {
+    vec![
+        ("eth_transfers", block(&[eth_transfer])),
+        ("erc20_transfers", block(&[erc20_transfer])),
+        ("swaps", block(&[swap])),
+        ("mixed", block(&[eth_transfer, erc20_transfer, erc20_transfer, swap])),
+        ("random_calldata", block(&[random_calldata])),
+    ]
+}
+
+fn compression(c: &mut Criterion) {
+    let mixes = mixes();
+
+    println!("{:<16} {:>10} {:>10} {:>7}", "mix", "plain", "zstd", "ratio");
+    for (name, txs) in &mixes {
+        let plain = encode_txns::<Alloy2718Coder>(txs).len();
+        let zstd = encode_txns::<ZstdCoder>(txs).len();
+        println!("{name:<16} {plain:>10} {zstd:>10} {:>7.3}", zstd as f64 / plain as f64);
+    }
+
+    for (name, txs) in &mixes {
+        let mut group = c.benchmark_group(*name);
+        group.sample_size(10);
+
+        group.bench_function("encode_plain", |b| {
+            b.iter(|| encode_txns::<Alloy2718Coder>(black_box(txs)))
+        });
+        group
+            .bench_function("encode_zstd", |b| b.iter(|| encode_txns::<ZstdCoder>(black_box(txs))));
+
+        let plain = encode_txns::<Alloy2718Coder>(txs);
+        let zstd = encode_txns::<ZstdCoder>(txs);
+        group.bench_function("decode_plain", |b| {
+            b.iter(|| decode_txns::<Alloy2718Coder>(black_box(&plain)))
+        });
+        group.bench_function("decode_zstd", |b| {
+            b.iter(|| decode_txns::<ZstdCoder>(black_box(&zstd)))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, compression);
+criterion_main!(benches);
@@ -27,6 +27,22 @@ mod zenith {
     impl Copy for Zenith::OnlySequencerAdmin {}
     impl Copy for Zenith::IncorrectHostBlock {}
 
+    impl Copy for Zenith::ZenithEvents {}
+
+    impl Clone for Zenith::ZenithEvents {
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+
+    impl Copy for Zenith::ZenithErrors {}
+
+    impl Clone for Zenith::ZenithErrors {
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+
     impl Zenith::BlockSubmitted {
         /// Get the sequencer address that signed the block.
         pub const fn sequencer(&self) -> Address {
@@ -132,6 +148,16 @@ mod passage {
         }
     }
 
+    impl Copy for Passage::ReentrancyGuardReentrantCall {}
+
+    impl Copy for Passage::PassageErrors {}
+
+    impl Clone for Passage::PassageErrors {
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+
     impl Passage::EnterToken {
         /// Get the chain ID of the event (discarding high bytes), returns `None`
         /// if the event has no associated chain id.
@@ -222,6 +248,16 @@ mod orders {
     impl Copy for Orders::OrderExpired {}
     impl Copy for Orders::OutputMismatch {}
     impl Copy for Orders::SafeERC20FailedOperation {}
+    impl Copy for Orders::FailedCall {}
+    impl Copy for Orders::ReentrancyGuardReentrantCall {}
+
+    impl Copy for Orders::OrdersErrors {}
+
+    impl Clone for Orders::OrdersErrors {
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
 
     impl Clone for Orders::OrdersEvents {
         fn clone(&self) -> Self {
@@ -310,6 +346,17 @@ mod transactor {
     );
 
     impl Copy for Transactor::GasConfigured {}
+    impl Copy for Transactor::OnlyGasAdmin {}
+    impl Copy for Transactor::PerBlockTransactGasLimit {}
+    impl Copy for Transactor::PerTransactGasLimit {}
+
+    impl Copy for Transactor::TransactorErrors {}
+
+    impl Clone for Transactor::TransactorErrors {
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
 
     impl Clone for Transactor::TransactorEvents {
         fn clone(&self) -> Self {
@@ -386,6 +433,39 @@ mod bundle_helper {
         BundleHelper,
         "abi/BundleHelper.json"
     );
+
+    impl Copy for Zenith::BlockHeader {}
+    impl Copy for IOrders::Output {}
+
+    impl From<Zenith::BlockHeader> for super::zenith::Zenith::BlockHeader {
+        fn from(header: Zenith::BlockHeader) -> Self {
+            Self {
+                rollupChainId: header.rollupChainId,
+                hostBlockNumber: header.hostBlockNumber,
+                gasLimit: header.gasLimit,
+                rewardAddress: header.rewardAddress,
+                blockDataHash: header.blockDataHash,
+            }
+        }
+    }
+
+    impl From<IOrders::Output> for super::orders::IOrders::Output {
+        fn from(output: IOrders::Output) -> Self {
+            Self {
+                token: output.token,
+                amount: output.amount,
+                recipient: output.recipient,
+                chainId: output.chainId,
+            }
+        }
+    }
+
+    impl BundleHelper::FillPermit2 {
+        /// Get the outputs of the fill.
+        pub fn outputs(&self) -> impl Iterator<Item = super::orders::IOrders::Output> + '_ {
+            self.outputs.iter().copied().map(Into::into)
+        }
+    }
 }
 
 pub use zenith::Zenith;
@@ -445,4 +525,8 @@ pub mod RollupPassage {
 pub mod BundleHelper {
     pub use super::bundle_helper::BundleHelper::*;
     pub use super::bundle_helper::Zenith::BlockHeader;
+
+    pub use super::bundle_helper::IOrders::*;
+    pub use super::bundle_helper::ISignatureTransfer::*;
+    pub use super::bundle_helper::UsesPermit2::*;
 }
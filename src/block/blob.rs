@@ -0,0 +1,128 @@
+use crate::Zenith::BlockHeader as ZenithHeader;
+use crate::{Coder, ZenithBlock};
+use alloy::consensus::{Blob, BlobTransactionSidecar, SidecarBuilder, SidecarCoder, SimpleCoder};
+use alloy::eips::eip4844::FIELD_ELEMENTS_PER_BLOB;
+
+/// Errors that may occur when packing or unpacking block data into blobs.
+#[derive(Debug, thiserror::Error)]
+pub enum BlobError {
+    /// Error computing the KZG commitments or proofs for the blobs.
+    #[error("kzg error: {0}")]
+    Kzg(#[from] c_kzg::Error),
+    /// The blobs did not contain validly-coded block data.
+    #[error("blobs are not validly coded")]
+    InvalidCoding,
+    /// The blobs contained a number of data items other than one.
+    #[error("expected exactly 1 data item in blobs, found {0}")]
+    UnexpectedItemCount(usize),
+}
+
+/// Calculate the number of blobs required to hold `data_len` bytes of block
+/// data, using the [`SimpleCoder`] packing.
+pub const fn blobs_required(data_len: usize) -> usize {
+    // SimpleCoder uses 1 FE for the length prefix, then 31 bytes per FE.
+    let fes = data_len.div_ceil(31) + 1;
+    fes.div_ceil(FIELD_ELEMENTS_PER_BLOB as usize)
+}
+
+/// Calculate the maximum number of bytes of block data that can be packed
+/// into `num_blobs` blobs, using the [`SimpleCoder`] packing.
+pub const fn blob_capacity(num_blobs: usize) -> usize {
+    (num_blobs * FIELD_ELEMENTS_PER_BLOB as usize).saturating_sub(1) * 31
+}
+
+impl<C> ZenithBlock<C>
+where
+    C: Coder,
+{
+    /// Decode a block from its header and the blobs that carry its data.
+    ///
+    /// The blobs must have been produced by [`ZenithBlock::blobs`] or
+    /// [`ZenithBlock::blob_sidecar`]. The resulting block has the same
+    /// [`ZenithBlock::block_data_hash`] as the block that was packed.
+    pub fn from_header_and_blobs(header: ZenithHeader, blobs: &[Blob]) -> Result<Self, BlobError> {
+        let mut items = SimpleCoder::default().decode_all(blobs).ok_or(BlobError::InvalidCoding)?;
+        if items.len() != 1 {
+            return Err(BlobError::UnexpectedItemCount(items.len()));
+        }
+        Ok(Self::from_header_and_data(header, items.pop().unwrap()))
+    }
+
+    /// Decode a block from its header and a [`BlobTransactionSidecar`].
+    ///
+    /// This does not verify the KZG proofs in the sidecar.
+    pub fn from_header_and_sidecar(
+        header: ZenithHeader,
+        sidecar: &BlobTransactionSidecar,
+    ) -> Result<Self, BlobError> {
+        Self::from_header_and_blobs(header, &sidecar.blobs)
+    }
+
+    /// The number of blobs required to hold the encoded transactions.
+    pub fn blob_count(&self) -> usize {
+        blobs_required(self.encoded_txns().len())
+    }
+
+    /// Pack the encoded transactions into blobs, without computing KZG
+    /// commitments.
+    pub fn blobs(&self) -> Vec<Blob> {
+        SidecarBuilder::<SimpleCoder>::from_slice(self.encoded_txns()).take()
+    }
+
+    /// Pack the encoded transactions into a [`BlobTransactionSidecar`],
+    /// computing KZG commitments and proofs for each blob.
+    pub fn blob_sidecar(&self) -> Result<BlobTransactionSidecar, BlobError> {
+        SidecarBuilder::<SimpleCoder>::from_slice(self.encoded_txns()).build().map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{header, tx};
+
+    #[test]
+    fn blob_roundtrip() {
+        let block: ZenithBlock = ZenithBlock::new(header(), vec![tx(0, 8), tx(1, 64)]);
+        assert_eq!(block.blob_count(), 1);
+
+        let blobs = block.blobs();
+        assert_eq!(blobs.len(), 1);
+
+        let decoded: ZenithBlock = ZenithBlock::from_header_and_blobs(header(), &blobs).unwrap();
+        assert_eq!(decoded.transactions(), block.transactions());
+        assert_eq!(decoded.encoded_txns(), block.encoded_txns());
+        assert_eq!(decoded.block_data_hash(), block.block_data_hash());
+    }
+
+    #[test]
+    fn multi_blob_sidecar_roundtrip() {
+        let txs = (0..3).map(|i| tx(i, 100_000)).collect();
+        let block: ZenithBlock = ZenithBlock::new(header(), txs);
+        let count = block.blob_count();
+        assert!(count > 1);
+
+        let sidecar = block.blob_sidecar().unwrap();
+        assert_eq!(sidecar.blobs.len(), count);
+        assert_eq!(sidecar.commitments.len(), count);
+        assert_eq!(sidecar.versioned_hashes().count(), count);
+
+        let decoded: ZenithBlock =
+            ZenithBlock::from_header_and_sidecar(header(), &sidecar).unwrap();
+        assert_eq!(decoded.transactions(), block.transactions());
+        assert_eq!(decoded.block_data_hash(), block.block_data_hash());
+    }
+
+    #[test]
+    fn capacity() {
+        assert_eq!(blobs_required(blob_capacity(1)), 1);
+        assert_eq!(blobs_required(blob_capacity(1) + 1), 2);
+        assert_eq!(blobs_required(blob_capacity(6)), 6);
+    }
+
+    #[test]
+    fn empty_blobs() {
+        let err = ZenithBlock::<crate::Alloy2718Coder>::from_header_and_blobs(header(), &[]);
+        assert!(matches!(err, Err(BlobError::InvalidCoding)));
+    }
+}
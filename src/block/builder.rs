@@ -0,0 +1,339 @@
+use crate::Zenith::BlockHeader as ZenithHeader;
+use crate::{
+    blob_capacity, Alloy2718Coder, Coder, PolicyViolation, TxDecodeError, TxPolicyConfig,
+    ZenithBlock,
+};
+use alloy::consensus::Transaction;
+use alloy::eips::eip4844::MAX_BLOBS_PER_BLOCK;
+use alloy::rlp::{length_of_length, Encodable};
+
+/// The minimum gas used by any transaction. When less than this amount of gas
+/// remains, the block is considered full.
+pub const MIN_TRANSACTION_GAS: u64 = 21_000;
+
+/// Reasons a [`ZenithBlockBuilder`] may refuse a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum BlockLimitError {
+    /// The transaction is for a different chain, or has no chain id.
+    #[error("wrong chain id: expected {expected}, got {got:?}")]
+    WrongChainId {
+        /// The chain id of the block.
+        expected: u64,
+        /// The chain id of the transaction.
+        got: Option<u64>,
+    },
+    /// The transaction gas limit exceeds the gas remaining in the block.
+    #[error("transaction gas {needed} exceeds remaining block gas {remaining}")]
+    GasLimit {
+        /// The gas limit of the transaction.
+        needed: u64,
+        /// The gas remaining in the block.
+        remaining: u64,
+    },
+    /// The transaction is disallowed by the coder, such as a [`PolicyCoder`],
+    /// or by the builder's [`TxPolicyConfig`].
+    ///
+    /// [`PolicyCoder`]: crate::PolicyCoder
+    #[error(transparent)]
+    Policy(#[from] PolicyViolation),
+    /// The coder does not decode the encoded transaction, so it would be
+    /// dropped from the block data by decoders.
+    #[error("transaction does not decode: {0}")]
+    Undecodable(TxDecodeError),
+    /// The encoded transaction would exceed the byte budget of the block.
+    #[error("transaction needs {needed} bytes, but only {remaining} remain")]
+    SizeLimit {
+        /// The number of bytes the transaction adds to the block data.
+        needed: usize,
+        /// The number of bytes remaining in the budget.
+        remaining: usize,
+    },
+}
+
+impl BlockLimitError {
+    /// True if the transaction was refused only because the block lacks the
+    /// gas or bytes to hold it, so it may fit in another block.
+    pub const fn is_capacity(&self) -> bool {
+        matches!(self, Self::GasLimit { .. } | Self::SizeLimit { .. })
+    }
+}
+
+/// A transaction refused by a [`ZenithBlockBuilder`], returned to the caller
+/// along with the reason.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("transaction rejected: {reason}")]
+pub struct RejectedTx<T: core::fmt::Debug> {
+    /// The rejected transaction.
+    pub tx: T,
+    /// The reason for rejection.
+    pub reason: BlockLimitError,
+}
+
+/// The length of an RLP list with the given payload length.
+const fn encoded_len(payload_len: usize) -> usize {
+    payload_len + length_of_length(payload_len)
+}
+
+/// Builds a [`ZenithBlock`] while enforcing the header's chain id and gas
+/// limit, and a byte budget on the encoded block data.
+///
+/// By default, the byte budget is the capacity of [`MAX_BLOBS_PER_BLOCK`]
+/// blobs. The budget applies to the RLP-encoded transaction list before
+/// [`Coder::compress`], so it is conservative for compressing coders.
+///
+/// Each transaction is checked to decode with the coder, so a transaction
+/// that decoders would drop, such as one disallowed by a [`PolicyCoder`], is
+/// refused rather than posted.
+///
+/// [`PolicyCoder`]: crate::PolicyCoder
+#[derive(Debug, Clone)]
+pub struct ZenithBlockBuilder<C: Coder = Alloy2718Coder> {
+    block: ZenithBlock<C>,
+    gas_used: u64,
+    /// Length of the RLP list payload, i.e. the sum of encoded item lengths.
+    payload_len: usize,
+    max_bytes: usize,
+    policy: Option<TxPolicyConfig>,
+}
+
+impl<C> ZenithBlockBuilder<C>
+where
+    C: Coder,
+    C::Tx: Transaction,
+{
+    /// Create a new builder for a block with the given header.
+    pub const fn new(header: ZenithHeader) -> Self {
+        Self {
+            block: ZenithBlock::new(header, Vec::new()),
+            gas_used: 0,
+            payload_len: 0,
+            max_bytes: blob_capacity(MAX_BLOBS_PER_BLOCK),
+            policy: None,
+        }
+    }
+
+    /// Refuse transactions disallowed by the policy, in addition to those the
+    /// coder does not decode.
+    pub fn with_policy(mut self, policy: TxPolicyConfig) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Set the byte budget for the encoded block data.
+    pub const fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Set the byte budget to the capacity of `num_blobs` blobs.
+    pub const fn with_max_blobs(self, num_blobs: usize) -> Self {
+        self.with_max_bytes(blob_capacity(num_blobs))
+    }
+
+    /// Access the block being built.
+    pub const fn block(&self) -> &ZenithBlock<C> {
+        &self.block
+    }
+
+    /// Get the cumulative gas limit of the transactions in the block.
+    pub const fn gas_used(&self) -> u64 {
+        self.gas_used
+    }
+
+    /// Get the gas remaining in the block.
+    pub const fn gas_remaining(&self) -> u64 {
+        self.block.gas_limit().saturating_sub(self.gas_used)
+    }
+
+    /// Get the byte budget for the encoded block data.
+    pub const fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Get the length of the encoded block data, i.e. the length of
+    /// [`ZenithBlock::encoded_txns`] before [`Coder::compress`].
+    pub const fn encoded_len(&self) -> usize {
+        encoded_len(self.payload_len)
+    }
+
+    /// Get the number of bytes remaining in the byte budget.
+    pub const fn bytes_remaining(&self) -> usize {
+        self.max_bytes.saturating_sub(self.encoded_len())
+    }
+
+    /// True if no further transaction could fit in the block.
+    pub const fn is_full(&self) -> bool {
+        self.gas_remaining() < MIN_TRANSACTION_GAS || self.bytes_remaining() == 0
+    }
+
+    /// Check the transaction against the block limits, as if the block had
+    /// used `gas_used` gas and had a list payload of `payload_len` bytes.
+    /// Returns the encoded length of the item if it would be accepted.
+    fn check_at(
+        &self,
+        gas_used: u64,
+        payload_len: usize,
+        tx: &C::Tx,
+    ) -> Result<usize, BlockLimitError> {
+        let encoded = C::encode(tx);
+        C::try_decode(&mut encoded.as_slice()).map_err(|e| match e {
+            TxDecodeError::Policy(violation) => BlockLimitError::Policy(violation),
+            e => BlockLimitError::Undecodable(e),
+        })?;
+        if let Some(policy) = &self.policy {
+            policy.check(tx)?;
+        }
+
+        let expected = self.block.chain_id();
+        if tx.chain_id() != Some(expected) {
+            return Err(BlockLimitError::WrongChainId { expected, got: tx.chain_id() });
+        }
+
+        let remaining = self.block.gas_limit().saturating_sub(gas_used);
+        if tx.gas_limit() > remaining {
+            return Err(BlockLimitError::GasLimit { needed: tx.gas_limit(), remaining });
+        }
+
+        let item_len = encoded.length();
+        let needed = encoded_len(payload_len + item_len) - encoded_len(payload_len);
+        let remaining = self.max_bytes.saturating_sub(encoded_len(payload_len));
+        if needed > remaining {
+            return Err(BlockLimitError::SizeLimit { needed, remaining });
+        }
+
+        Ok(item_len)
+    }
+
+    /// Check whether the transaction would be accepted by the builder.
+    pub fn check(&self, tx: &C::Tx) -> Result<(), BlockLimitError> {
+        self.check_at(self.gas_used, self.payload_len, tx).map(|_| ())
+    }
+
+    /// Check whether the transactions would all be accepted by the builder,
+    /// if pushed in order. Returns the index of the first transaction that
+    /// would be refused, and the reason.
+    pub fn check_bundle(&self, txs: &[C::Tx]) -> Result<(), (usize, BlockLimitError)> {
+        self.check_bundle_inner(txs).map(|_| ())
+    }
+
+    /// Returns the gas and payload length of the block after the bundle.
+    fn check_bundle_inner(&self, txs: &[C::Tx]) -> Result<(u64, usize), (usize, BlockLimitError)> {
+        txs.iter().enumerate().try_fold(
+            (self.gas_used, self.payload_len),
+            |(gas_used, payload_len), (i, tx)| {
+                let item_len = self.check_at(gas_used, payload_len, tx).map_err(|e| (i, e))?;
+                Ok((gas_used + tx.gas_limit(), payload_len + item_len))
+            },
+        )
+    }
+
+    /// Push a transaction into the block, if it fits within the block limits.
+    /// Otherwise, return it along with the reason it was rejected.
+    pub fn push_transaction(&mut self, tx: C::Tx) -> Result<(), RejectedTx<C::Tx>> {
+        match self.check_at(self.gas_used, self.payload_len, &tx) {
+            Ok(item_len) => {
+                self.gas_used += tx.gas_limit();
+                self.payload_len += item_len;
+                self.block.push_transaction(tx);
+                Ok(())
+            }
+            Err(reason) => Err(RejectedTx { tx, reason }),
+        }
+    }
+
+    /// Push a bundle of transactions into the block atomically. Either every
+    /// transaction fits within the block limits and is pushed in order, or
+    /// none are pushed and the bundle is returned along with the reason the
+    /// first refused transaction was rejected.
+    pub fn push_bundle(&mut self, txs: Vec<C::Tx>) -> Result<(), RejectedTx<Vec<C::Tx>>> {
+        match self.check_bundle_inner(&txs) {
+            Ok((gas_used, payload_len)) => {
+                self.gas_used = gas_used;
+                self.payload_len = payload_len;
+                self.block.transactions_mut().extend(txs);
+                Ok(())
+            }
+            Err((_, reason)) => Err(RejectedTx { tx: txs, reason }),
+        }
+    }
+
+    /// Finish building, returning the block.
+    pub fn build(self) -> ZenithBlock<C> {
+        self.block
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{header, tx};
+    use alloy::primitives::U256;
+
+    #[test]
+    fn tracks_encoded_len() {
+        let mut builder = ZenithBlockBuilder::<Alloy2718Coder>::new(header());
+        assert_eq!(builder.encoded_len(), builder.block().encoded_txns().len());
+
+        for (i, len) in [0, 10, 100, 1000].into_iter().enumerate() {
+            builder.push_transaction(tx(i as u64, len)).unwrap();
+            assert_eq!(builder.encoded_len(), builder.block().encoded_txns().len());
+        }
+        assert_eq!(builder.gas_used(), 4 * 21_000);
+        assert_eq!(builder.build().transactions().len(), 4);
+    }
+
+    #[test]
+    fn gas_limit() {
+        let mut h = header();
+        h.gasLimit = U256::from(50_000);
+        let mut builder = ZenithBlockBuilder::<Alloy2718Coder>::new(h);
+
+        builder.push_transaction(tx(0, 0)).unwrap();
+        builder.push_transaction(tx(1, 0)).unwrap();
+        assert!(builder.is_full());
+
+        let err = builder.push_transaction(tx(2, 0)).unwrap_err();
+        assert_eq!(err.tx, tx(2, 0));
+        assert_eq!(err.reason, BlockLimitError::GasLimit { needed: 21_000, remaining: 8_000 });
+    }
+
+    #[test]
+    fn size_limit() {
+        let mut builder = ZenithBlockBuilder::<Alloy2718Coder>::new(header()).with_max_bytes(500);
+
+        builder.push_transaction(tx(0, 100)).unwrap();
+        let err = builder.push_transaction(tx(1, 200)).unwrap_err();
+        assert!(matches!(err.reason, BlockLimitError::SizeLimit { .. }));
+        assert!(builder.encoded_len() <= builder.max_bytes());
+    }
+
+    #[test]
+    fn bundle() {
+        let mut h = header();
+        h.gasLimit = U256::from(70_000);
+        let mut builder = ZenithBlockBuilder::<Alloy2718Coder>::new(h);
+
+        builder.push_bundle(vec![tx(0, 0), tx(1, 0)]).unwrap();
+        assert_eq!(builder.gas_used(), 42_000);
+        assert_eq!(builder.encoded_len(), builder.block().encoded_txns().len());
+
+        // the second transaction does not fit, so neither is pushed
+        let err = builder.push_bundle(vec![tx(2, 0), tx(3, 0)]).unwrap_err();
+        assert_eq!(err.tx, vec![tx(2, 0), tx(3, 0)]);
+        assert_eq!(err.reason, BlockLimitError::GasLimit { needed: 21_000, remaining: 7_000 });
+        assert_eq!(builder.check_bundle(&err.tx), Err((1, err.reason)));
+        assert_eq!(builder.block().transactions().len(), 2);
+    }
+
+    #[test]
+    fn chain_id() {
+        let mut h = header();
+        h.rollupChainId = U256::from(1);
+        let builder = ZenithBlockBuilder::<Alloy2718Coder>::new(h);
+
+        assert_eq!(
+            builder.check(&tx(0, 0)),
+            Err(BlockLimitError::WrongChainId { expected: 1, got: Some(17) })
+        );
+    }
+}
@@ -0,0 +1,211 @@
+/// Errors produced when decompressing block data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum DecompressionError {
+    /// The decompressed data exceeds the size cap.
+    #[error("decompressed data exceeds {max} bytes")]
+    TooLarge {
+        /// The maximum decompressed size.
+        max: usize,
+    },
+    /// The data is not validly compressed.
+    #[error("invalid compressed data")]
+    Invalid,
+    /// The data does not carry the expected version prefix. `None` indicates
+    /// legacy data with no prefix.
+    #[error("unexpected block data version: expected {expected:#04x}, got {got:?}")]
+    UnexpectedVersion {
+        /// The expected version.
+        expected: u8,
+        /// The version of the data.
+        got: Option<u8>,
+    },
+}
+
+/// The default cap on the size of decompressed block data, used by
+/// [`DecodeLimits::default`] and `ZstdCoder`. This is comfortably larger than
+/// the RLP encoding of a block whose calldata consumes a 30M gas limit.
+///
+/// [`DecodeLimits::default`]: crate::DecodeLimits::default
+pub const DEFAULT_MAX_DECOMPRESSED_SIZE: usize = 8 * 1024 * 1024;
+
+#[cfg(feature = "zstd")]
+pub use zstd_coder::{ZstdCoder, ZSTD_LEVEL};
+
+#[cfg(feature = "zstd")]
+mod zstd_coder {
+    use super::{DecompressionError, DEFAULT_MAX_DECOMPRESSED_SIZE};
+    use crate::{Alloy2718Coder, Coder, TxDecodeError, VersionedCoder, ZSTD_BLOCK_DATA_VERSION};
+    use std::{borrow::Cow, io::Read, marker::PhantomData};
+
+    /// The zstd compression level used by [`ZstdCoder`].
+    pub const ZSTD_LEVEL: i32 = 19;
+
+    /// Coder that zstd-compresses the block data produced by an inner coder.
+    ///
+    /// Transactions are encoded by the inner coder `C`, and the resulting RLP
+    /// list is compressed as a whole. Decompression fails if the output would
+    /// exceed `MAX` bytes.
+    ///
+    /// The compressed data is prefixed with [`ZSTD_BLOCK_DATA_VERSION`], so it
+    /// is decoded by [`DecoderRegistry::for_coder`], and never starts with
+    /// the zstd magic number.
+    ///
+    /// Because [`ZenithBlock`] hashes the encoded block data, the
+    /// `blockDataHash` of a block using this coder commits to the compressed
+    /// bytes.
+    ///
+    /// # Panics
+    ///
+    /// Encoding panics if zstd fails to compress the block data. Compressing
+    /// from one in-memory buffer into another only fails if zstd cannot
+    /// allocate its context.
+    ///
+    /// [`DecoderRegistry::for_coder`]: crate::DecoderRegistry::for_coder
+    /// [`ZenithBlock`]: crate::ZenithBlock
+    pub type ZstdCoder<C = Alloy2718Coder, const MAX: usize = DEFAULT_MAX_DECOMPRESSED_SIZE> =
+        VersionedCoder<ZSTD_BLOCK_DATA_VERSION, RawZstdCoder<C, MAX>>;
+
+    /// The unversioned zstd coder wrapped by [`ZstdCoder`]. It is not
+    /// exported, so block data is never compressed without a version.
+    #[derive(Debug, Clone, Copy)]
+    pub struct RawZstdCoder<C, const MAX: usize>(PhantomData<C>);
+
+    impl<C, const MAX: usize> Coder for RawZstdCoder<C, MAX>
+    where
+        C: Coder,
+    {
+        type Tx = C::Tx;
+
+        fn encode(t: &Self::Tx) -> Vec<u8> {
+            C::encode(t)
+        }
+
+        fn encode_into(t: &Self::Tx, out: &mut Vec<u8>) {
+            C::encode_into(t, out)
+        }
+
+        fn decode(buf: &mut &[u8]) -> Option<Self::Tx>
+        where
+            Self: Sized,
+        {
+            C::decode(buf)
+        }
+
+        fn try_decode(buf: &mut &[u8]) -> Result<Self::Tx, TxDecodeError>
+        where
+            Self: Sized,
+        {
+            C::try_decode(buf)
+        }
+
+        fn decode_with<E>(
+            buf: &mut &[u8],
+            check: impl FnOnce(&Self::Tx) -> Result<(), E>,
+        ) -> Result<Option<Self::Tx>, E>
+        where
+            Self: Sized,
+        {
+            C::decode_with(buf, check)
+        }
+
+        fn compress(data: Vec<u8>) -> Vec<u8> {
+            // Compressing from an in-memory buffer into a Vec only fails if
+            // zstd cannot allocate its context.
+            zstd::encode_all(data.as_slice(), ZSTD_LEVEL).expect("zstd compression failed")
+        }
+
+        fn decompress(data: &[u8]) -> Result<Cow<'_, [u8]>, DecompressionError> {
+            Self::decompress_bounded(data, MAX)
+        }
+
+        /// Decompress at most `max` bytes, or `MAX` if it is lower.
+        fn decompress_bounded(
+            data: &[u8],
+            max: usize,
+        ) -> Result<Cow<'_, [u8]>, DecompressionError> {
+            let max = max.min(MAX);
+            let decoder =
+                zstd::stream::read::Decoder::new(data).map_err(|_| DecompressionError::Invalid)?;
+            // Read one byte past the cap, so that oversized data is detected
+            // without decompressing all of it.
+            let mut out = Vec::new();
+            decoder
+                .take(max as u64 + 1)
+                .read_to_end(&mut out)
+                .map_err(|_| DecompressionError::Invalid)?;
+            if out.len() > max {
+                return Err(DecompressionError::TooLarge { max });
+            }
+            Ok(Cow::Owned(out))
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use crate::test_utils::{header, tx};
+        use crate::{decode_txns, decode_txns_strict, encode_txns, ZenithBlock};
+
+        #[test]
+        fn roundtrip() {
+            let txs = vec![tx(0, 0), tx(1, 100), tx(2, 1000)];
+            let plain = encode_txns::<Alloy2718Coder>(&txs);
+            let compressed = encode_txns::<ZstdCoder>(&txs);
+            assert!(compressed.len() < plain.len());
+            assert_eq!(decode_txns::<ZstdCoder>(&compressed), txs);
+
+            let block: ZenithBlock<ZstdCoder> = ZenithBlock::new(header(), txs.clone());
+            assert_eq!(block.encoded_txns(), compressed.as_slice());
+            let decoded: ZenithBlock<ZstdCoder> =
+                ZenithBlock::from_header_and_data(header(), block.encoded_txns());
+            assert_eq!(decoded.transactions(), txs.as_slice());
+            assert_eq!(decoded.block_data_hash(), block.block_data_hash());
+        }
+
+        #[test]
+        fn size_cap() {
+            let txs = vec![tx(0, 10_000)];
+            let compressed = encode_txns::<ZstdCoder>(&txs);
+
+            assert_eq!(
+                ZstdCoder::<Alloy2718Coder, 1024>::decompress(&compressed),
+                Err(DecompressionError::TooLarge { max: 1024 })
+            );
+            assert!(decode_txns::<ZstdCoder<Alloy2718Coder, 1024>>(&compressed).is_empty());
+            assert_eq!(
+                ZstdCoder::<Alloy2718Coder>::decompress_bounded(&compressed, 512),
+                Err(DecompressionError::TooLarge { max: 512 })
+            );
+            assert_eq!(
+                ZstdCoder::<Alloy2718Coder, 1024>::decompress_bounded(&compressed, 4096),
+                Err(DecompressionError::TooLarge { max: 1024 })
+            );
+
+            let report = decode_txns_strict::<ZstdCoder<Alloy2718Coder, 1024>>(&compressed);
+            assert_eq!(
+                report.failures[0].error,
+                TxDecodeError::Decompression(DecompressionError::TooLarge { max: 1024 })
+            );
+        }
+
+        #[test]
+        fn junk() {
+            let plain = encode_txns::<Alloy2718Coder>(&[tx(0, 0)]);
+            assert_eq!(
+                ZstdCoder::<Alloy2718Coder>::decompress(&plain),
+                Err(DecompressionError::UnexpectedVersion {
+                    expected: ZSTD_BLOCK_DATA_VERSION,
+                    got: None
+                })
+            );
+            assert!(decode_txns::<ZstdCoder>(&plain).is_empty());
+
+            let mut versioned = vec![ZSTD_BLOCK_DATA_VERSION];
+            versioned.extend_from_slice(&plain);
+            assert_eq!(
+                ZstdCoder::<Alloy2718Coder>::decompress(&versioned),
+                Err(DecompressionError::Invalid)
+            );
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use crate::Zenith::BlockHeader as ZenithHeader;
+use crate::{Coder, DecompressionError, PolicyViolation, TxItems, ZenithBlock};
+use alloy::primitives::keccak256;
+use std::{marker::PhantomData, sync::OnceLock};
+
+/// Reasons a transaction may fail to decode from block data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum TxDecodeError {
+    /// The outer RLP structure of the block data or the item was invalid.
+    #[error("invalid outer rlp: {0}")]
+    OuterRlp(alloy::rlp::Error),
+    /// The item was not a valid EIP-2718 envelope.
+    #[error("invalid 2718 envelope: {0}")]
+    Inner2718(alloy::rlp::Error),
+    /// The item had a transaction type the coder does not support.
+    #[error("unsupported transaction type: {0:#04x}")]
+    UnsupportedType(u8),
+    /// Bytes remained after decoding the item.
+    #[error("{0} trailing bytes")]
+    TrailingBytes(usize),
+    /// The block data could not be decompressed.
+    #[error("failed to decompress block data: {0}")]
+    Decompression(DecompressionError),
+    /// The transaction is disallowed by a policy, such as that of a
+    /// [`PolicyCoder`].
+    ///
+    /// [`PolicyCoder`]: crate::PolicyCoder
+    #[error(transparent)]
+    Policy(PolicyViolation),
+    /// The coder rejected the item without giving a reason.
+    #[error("rejected by coder")]
+    Rejected,
+}
+
+/// A single item that failed to decode from block data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxDecodeFailure {
+    /// The index of the item in the outer RLP list.
+    pub index: usize,
+    /// The byte offset of the item in the block data, after
+    /// [`Coder::decompress`].
+    pub offset: usize,
+    /// Why the item failed to decode.
+    pub error: TxDecodeError,
+}
+
+impl core::fmt::Display for TxDecodeFailure {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "item {} at offset {}: {}", self.index, self.offset, self.error)
+    }
+}
+
+/// The result of strictly decoding block data, produced by
+/// [`decode_txns_strict`].
+///
+/// Contains the transactions that decoded successfully, in order, and a
+/// [`TxDecodeFailure`] for each item that did not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeReport<T> {
+    /// Successfully decoded transactions.
+    pub transactions: Vec<T>,
+    /// Items that failed to decode.
+    pub failures: Vec<TxDecodeFailure>,
+}
+
+impl<T> Default for DecodeReport<T> {
+    fn default() -> Self {
+        Self { transactions: Vec::new(), failures: Vec::new() }
+    }
+}
+
+impl<T> DecodeReport<T> {
+    /// True if every item decoded successfully.
+    pub fn is_clean(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Convert the report into a result, returning the transactions if every
+    /// item decoded successfully.
+    pub fn into_result(self) -> Result<Vec<T>, Self> {
+        if self.is_clean() {
+            Ok(self.transactions)
+        } else {
+            Err(self)
+        }
+    }
+
+    fn fail(&mut self, index: usize, offset: usize, error: TxDecodeError) {
+        self.failures.push(TxDecodeFailure { index, offset, error });
+    }
+}
+
+/// Decode transactions, reporting every item that fails.
+///
+/// Unlike [`decode_txns`], this function does not silently discard data. It
+/// will:
+/// - Report a failure of [`Coder::decompress`] as a failure of item 0, and
+///   stop
+/// - Report an invalid outer RLP list header as a failure of item 0, and stop
+/// - Report an item whose RLP header cannot be read, and stop, as the
+///   following items cannot be located
+/// - Report an item whose payload is not a valid byte list, and continue
+/// - Report an item that the [`Coder`] fails to decode, and continue
+/// - Report an item with trailing bytes after the transaction, and continue
+/// - Report any trailing bytes after the outer RLP list
+///
+/// [`decode_txns`]: crate::decode_txns
+pub fn decode_txns_strict<C>(block_data: impl AsRef<[u8]>) -> DecodeReport<C::Tx>
+where
+    C: Coder,
+{
+    decode_txns_strict_with::<C>(block_data.as_ref(), |_| Ok(()))
+}
+
+/// [`decode_txns_strict`], additionally reporting the transactions rejected
+/// by `check`.
+pub(crate) fn decode_txns_strict_with<C>(
+    block_data: &[u8],
+    check: impl Fn(&C::Tx) -> Result<(), PolicyViolation>,
+) -> DecodeReport<C::Tx>
+where
+    C: Coder,
+{
+    let mut report = DecodeReport::default();
+    let data = match C::decompress(block_data) {
+        Ok(data) => data,
+        Err(e) => {
+            report.fail(0, 0, TxDecodeError::Decompression(e));
+            return report;
+        }
+    };
+    let data = data.as_ref();
+
+    let mut items = match TxItems::new(data) {
+        Ok(items) => items,
+        Err(e) => {
+            report.fail(0, 0, e);
+            return report;
+        }
+    };
+
+    let mut scratch = Vec::new();
+    for item in items.by_ref() {
+        match item {
+            Ok(item) => match item.decode::<C>(&mut scratch).and_then(|tx| {
+                check(&tx).map_err(TxDecodeError::Policy)?;
+                Ok(tx)
+            }) {
+                Ok(tx) => report.transactions.push(tx),
+                Err(e) => report.fail(item.index, item.offset, e),
+            },
+            Err(failure) => {
+                report.failures.push(failure);
+                return report;
+            }
+        }
+    }
+
+    if items.trailing_bytes() != 0 {
+        report.fail(
+            items.index(),
+            items.list_end(),
+            TxDecodeError::TrailingBytes(items.trailing_bytes()),
+        );
+    }
+
+    report
+}
+
+impl<C> ZenithBlock<C>
+where
+    C: Coder,
+{
+    /// Strictly decode tx data in the block.
+    ///
+    /// Returns the block if every item decoded successfully. Otherwise returns
+    /// the [`DecodeReport`] describing each failure. See
+    /// [`decode_txns_strict`] for details.
+    pub fn from_header_and_data_strict(
+        header: ZenithHeader,
+        buf: impl AsRef<[u8]>,
+    ) -> Result<Self, DecodeReport<C::Tx>> {
+        let b = buf.as_ref();
+        let transactions = decode_txns_strict::<C>(b).into_result()?;
+        Ok(ZenithBlock {
+            header,
+            transactions,
+            encoded: b.to_owned().into(),
+            block_data_hash: keccak256(b).into(),
+            senders: OnceLock::new(),
+            _pd: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{header, tx};
+    use crate::{encode_txns, Alloy2718Coder};
+    use alloy::eips::eip2718::Encodable2718;
+    use alloy::rlp::Encodable;
+
+    fn encode_items(items: &[Vec<u8>]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        items.to_vec().encode(&mut buf);
+        buf
+    }
+
+    #[test]
+    fn clean() {
+        let txs = vec![tx(0, 0), tx(1, 0)];
+        let encoded = encode_txns::<Alloy2718Coder>(&txs);
+        let report = decode_txns_strict::<Alloy2718Coder>(&encoded);
+        assert!(report.is_clean());
+        assert_eq!(report.transactions, txs);
+    }
+
+    #[test]
+    fn outer_junk() {
+        let report = decode_txns_strict::<Alloy2718Coder>([0x01, 0x02, 0x03]);
+        assert!(report.transactions.is_empty());
+        assert_eq!(report.failures.len(), 1);
+        assert!(matches!(report.failures[0].error, TxDecodeError::OuterRlp(_)));
+    }
+
+    #[test]
+    fn item_failures() {
+        let good = tx(0, 0).encoded_2718();
+        let mut trailing = tx(1, 0).encoded_2718();
+        trailing.push(0);
+        let unsupported = vec![0x55, 0xc0];
+        let bad_inner = vec![0x02, 0xc1, 0x01];
+
+        let items = vec![good.clone(), trailing, unsupported, bad_inner, good];
+        let encoded = encode_items(&items);
+        let report = decode_txns_strict::<Alloy2718Coder>(&encoded);
+
+        assert_eq!(report.transactions, vec![tx(0, 0), tx(0, 0)]);
+        assert_eq!(report.failures.len(), 3);
+
+        assert_eq!(report.failures[0].index, 1);
+        assert_eq!(report.failures[0].error, TxDecodeError::TrailingBytes(1));
+        assert_eq!(report.failures[1].index, 2);
+        assert_eq!(report.failures[1].error, TxDecodeError::UnsupportedType(0x55));
+        assert_eq!(report.failures[2].index, 3);
+        assert!(matches!(report.failures[2].error, TxDecodeError::Inner2718(_)));
+
+        // offsets point at the start of each item
+        let first_len = {
+            let mut buf = Vec::new();
+            items[0].encode(&mut buf);
+            buf.len()
+        };
+        let list_header_len = encoded.len() - items.iter().map(Encodable::length).sum::<usize>();
+        assert_eq!(report.failures[0].offset, list_header_len + first_len);
+
+        // the lenient decoder drops the unsupported and malformed items
+        assert_eq!(crate::decode_txns::<Alloy2718Coder>(&encoded).len(), 3);
+    }
+
+    #[test]
+    fn trailing_after_list() {
+        let mut encoded = encode_txns::<Alloy2718Coder>(&[tx(0, 0)]);
+        let list_end = encoded.len();
+        encoded.extend_from_slice(&[1, 2]);
+
+        let report = decode_txns_strict::<Alloy2718Coder>(&encoded);
+        assert_eq!(report.transactions, vec![tx(0, 0)]);
+        assert_eq!(
+            report.failures,
+            vec![TxDecodeFailure {
+                index: 1,
+                offset: list_end,
+                error: TxDecodeError::TrailingBytes(2)
+            }]
+        );
+
+        let block = ZenithBlock::<Alloy2718Coder>::from_header_and_data_strict(header(), &encoded);
+        assert!(block.is_err());
+    }
+}
@@ -0,0 +1,271 @@
+use crate::Zenith::BlockHeader as ZenithHeader;
+use crate::{decode_txns_strict, Alloy2718Coder, Coder, DecodeReport, DecompressionError};
+use crate::{TxDecodeError, ZenithBlock};
+use alloy::primitives::keccak256;
+use std::{borrow::Cow, collections::BTreeMap, marker::PhantomData, sync::OnceLock};
+
+/// The highest valid block data version.
+///
+/// Legacy block data is a bare RLP list, which always starts with a byte of
+/// at least `0xc0`. Versioned block data starts with a version byte below
+/// `0xc0`, so the two can always be told apart.
+pub const MAX_BLOCK_DATA_VERSION: u8 = 0xbf;
+
+/// The block data version for zstd-compressed transactions, produced by
+/// [`ZstdCoder`].
+///
+/// [`ZstdCoder`]: crate::ZstdCoder
+#[cfg(feature = "zstd")]
+pub const ZSTD_BLOCK_DATA_VERSION: u8 = 1;
+
+/// Get the version of block data. Returns `None` for legacy data, which has
+/// no version prefix.
+pub fn block_data_version(data: &[u8]) -> Option<u8> {
+    data.first().copied().filter(|&v| v <= MAX_BLOCK_DATA_VERSION)
+}
+
+/// Coder that prefixes the block data produced by an inner coder with a
+/// version byte.
+///
+/// Decoding fails with [`DecompressionError::UnexpectedVersion`] if the data
+/// does not start with `VERSION`. Use a [`DecoderRegistry`] to decode block
+/// data of any known version.
+///
+/// `VERSION` must not exceed [`MAX_BLOCK_DATA_VERSION`].
+#[derive(Debug, Clone, Copy)]
+pub struct VersionedCoder<const VERSION: u8, C = Alloy2718Coder>(PhantomData<C>);
+
+impl<const VERSION: u8, C> Coder for VersionedCoder<VERSION, C>
+where
+    C: Coder,
+{
+    type Tx = C::Tx;
+
+    fn encode(t: &Self::Tx) -> Vec<u8> {
+        C::encode(t)
+    }
+
+    fn encode_into(t: &Self::Tx, out: &mut Vec<u8>) {
+        C::encode_into(t, out)
+    }
+
+    fn decode(buf: &mut &[u8]) -> Option<Self::Tx>
+    where
+        Self: Sized,
+    {
+        C::decode(buf)
+    }
+
+    fn try_decode(buf: &mut &[u8]) -> Result<Self::Tx, TxDecodeError>
+    where
+        Self: Sized,
+    {
+        C::try_decode(buf)
+    }
+
+    fn decode_with<E>(
+        buf: &mut &[u8],
+        check: impl FnOnce(&Self::Tx) -> Result<(), E>,
+    ) -> Result<Option<Self::Tx>, E>
+    where
+        Self: Sized,
+    {
+        C::decode_with(buf, check)
+    }
+
+    fn compress(data: Vec<u8>) -> Vec<u8> {
+        const { assert!(VERSION <= MAX_BLOCK_DATA_VERSION, "invalid block data version") };
+        let inner = C::compress(data);
+        let mut buf = Vec::with_capacity(inner.len() + 1);
+        buf.push(VERSION);
+        buf.extend_from_slice(&inner);
+        buf
+    }
+
+    fn decompress(data: &[u8]) -> Result<Cow<'_, [u8]>, DecompressionError> {
+        match block_data_version(data) {
+            Some(v) if v == VERSION => C::decompress(&data[1..]),
+            got => Err(DecompressionError::UnexpectedVersion { expected: VERSION, got }),
+        }
+    }
+
+    fn decompress_bounded(data: &[u8], max: usize) -> Result<Cow<'_, [u8]>, DecompressionError> {
+        match block_data_version(data) {
+            Some(v) if v == VERSION => C::decompress_bounded(&data[1..], max),
+            got => Err(DecompressionError::UnexpectedVersion { expected: VERSION, got }),
+        }
+    }
+}
+
+/// A function that decodes block data. It receives the entire block data,
+/// including any version prefix.
+pub type BlockDataDecoder<T> = fn(&[u8]) -> DecodeReport<T>;
+
+/// Error returned by a [`DecoderRegistry`] when block data has a version with
+/// no registered decoder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("unknown block data version {0:#04x}")]
+pub struct UnknownVersion(pub u8);
+
+/// A set of block data decoders, keyed by version, with a fallback for
+/// legacy data.
+///
+/// This allows nodes to derive blocks posted both before and after a change
+/// in the block data format.
+#[derive(Debug, Clone)]
+pub struct DecoderRegistry<T> {
+    legacy: BlockDataDecoder<T>,
+    versions: BTreeMap<u8, BlockDataDecoder<T>>,
+}
+
+impl<T> DecoderRegistry<T> {
+    /// Create a registry that decodes legacy data with the given decoder, and
+    /// has no versioned decoders.
+    pub const fn new(legacy: BlockDataDecoder<T>) -> Self {
+        Self { legacy, versions: BTreeMap::new() }
+    }
+
+    /// Create a registry for the known versions of block data produced by
+    /// the coder `C`. Legacy data is decoded by `C`. With the `zstd` feature,
+    /// `ZSTD_BLOCK_DATA_VERSION` is decoded by `ZstdCoder<C>`.
+    pub fn for_coder<C>() -> Self
+    where
+        C: Coder<Tx = T>,
+    {
+        #[allow(unused_mut)]
+        let mut registry = Self::new(|data| decode_txns_strict::<C>(data));
+        #[cfg(feature = "zstd")]
+        registry.register(ZSTD_BLOCK_DATA_VERSION, |data| {
+            decode_txns_strict::<crate::ZstdCoder<C>>(data)
+        });
+        registry
+    }
+
+    /// Register a decoder for a version, returning the decoder it replaces,
+    /// if any.
+    ///
+    /// # Panics
+    ///
+    /// If `version` exceeds [`MAX_BLOCK_DATA_VERSION`].
+    pub fn register(
+        &mut self,
+        version: u8,
+        decoder: BlockDataDecoder<T>,
+    ) -> Option<BlockDataDecoder<T>> {
+        assert!(version <= MAX_BLOCK_DATA_VERSION, "invalid block data version");
+        self.versions.insert(version, decoder)
+    }
+
+    /// Register a decoder for a version. See [`DecoderRegistry::register`].
+    pub fn with_version(mut self, version: u8, decoder: BlockDataDecoder<T>) -> Self {
+        self.register(version, decoder);
+        self
+    }
+
+    /// Get the decoder for a version, or the legacy decoder if `version` is
+    /// `None`.
+    pub fn get(&self, version: Option<u8>) -> Option<BlockDataDecoder<T>> {
+        match version {
+            Some(v) => self.versions.get(&v).copied(),
+            None => Some(self.legacy),
+        }
+    }
+
+    /// Iterate over the registered versions, in ascending order.
+    pub fn versions(&self) -> impl Iterator<Item = u8> + '_ {
+        self.versions.keys().copied()
+    }
+
+    /// Decode block data with the decoder for its version.
+    pub fn decode(&self, data: &[u8]) -> Result<DecodeReport<T>, UnknownVersion> {
+        let version = block_data_version(data);
+        let decoder = self.get(version).ok_or(UnknownVersion(version.unwrap_or_default()))?;
+        Ok(decoder(data))
+    }
+}
+
+impl<C> ZenithBlock<C>
+where
+    C: Coder,
+{
+    /// Decode tx data in the block, using the decoder registered for its
+    /// version.
+    ///
+    /// Like [`ZenithBlock::from_header_and_data`], items that fail to decode
+    /// are discarded. The block data hash is computed over the data as
+    /// posted, including any version prefix.
+    pub fn from_header_and_versioned_data(
+        header: ZenithHeader,
+        buf: impl AsRef<[u8]>,
+        registry: &DecoderRegistry<C::Tx>,
+    ) -> Result<Self, UnknownVersion> {
+        let b = buf.as_ref();
+        let transactions = registry.decode(b)?.transactions;
+        Ok(ZenithBlock {
+            header,
+            transactions,
+            encoded: b.to_owned().into(),
+            block_data_hash: keccak256(b).into(),
+            senders: OnceLock::new(),
+            _pd: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encode_txns;
+    use crate::test_utils::{header, tx};
+
+    type V7 = VersionedCoder<7>;
+
+    #[test]
+    fn prefix() {
+        let txs = vec![tx(0, 10), tx(1, 20)];
+        let legacy = encode_txns::<Alloy2718Coder>(&txs);
+        let versioned = encode_txns::<V7>(&txs);
+
+        assert_eq!(block_data_version(&legacy), None);
+        assert_eq!(block_data_version(&versioned), Some(7));
+        assert_eq!(&versioned[1..], legacy.as_slice());
+
+        assert_eq!(crate::decode_txns::<V7>(&versioned), txs);
+        assert!(crate::decode_txns::<V7>(&legacy).is_empty());
+        assert_eq!(
+            V7::decompress(&legacy),
+            Err(DecompressionError::UnexpectedVersion { expected: 7, got: None })
+        );
+    }
+
+    #[test]
+    fn registry() {
+        let txs = vec![tx(0, 10), tx(1, 20)];
+        let registry = DecoderRegistry::for_coder::<Alloy2718Coder>()
+            .with_version(7, |data| decode_txns_strict::<V7>(data));
+
+        let legacy: ZenithBlock = ZenithBlock::new(header(), txs.clone());
+        let versioned: ZenithBlock<V7> = ZenithBlock::new(header(), txs.clone());
+
+        for data in [legacy.encoded_txns(), versioned.encoded_txns()] {
+            let block: ZenithBlock =
+                ZenithBlock::from_header_and_versioned_data(header(), data, &registry).unwrap();
+            assert_eq!(block.transactions(), txs.as_slice());
+            assert_eq!(block.block_data_hash(), keccak256(data));
+        }
+
+        let mut unknown = legacy.encoded_txns().to_vec();
+        unknown.insert(0, 8);
+        assert_eq!(registry.decode(&unknown).unwrap_err(), UnknownVersion(8));
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd() {
+        let txs = vec![tx(0, 100), tx(1, 200)];
+        let data = encode_txns::<crate::ZstdCoder>(&txs);
+        assert_eq!(block_data_version(&data), Some(ZSTD_BLOCK_DATA_VERSION));
+        let registry = DecoderRegistry::for_coder::<Alloy2718Coder>();
+        assert_eq!(registry.decode(&data).unwrap().transactions, txs);
+    }
+}
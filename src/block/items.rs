@@ -0,0 +1,297 @@
+use crate::{Coder, TxDecodeError, TxDecodeFailure};
+use alloy::rlp::{Decodable, Header};
+
+/// A borrowing iterator over the items of an RLP-encoded transaction list.
+///
+/// Each item borrows its raw RLP encoding from the block data, so items can
+/// be skipped, hashed, or decoded lazily without allocating. The iterator
+/// stops after yielding the first error, as the following items cannot be
+/// located.
+///
+/// The iterator operates on the RLP list, i.e. on block data after
+/// [`Coder::decompress`].
+#[derive(Debug, Clone)]
+pub struct TxItems<'a> {
+    data: &'a [u8],
+    rest: &'a [u8],
+    list_end: usize,
+    index: usize,
+}
+
+impl<'a> TxItems<'a> {
+    /// Read the outer list header of the block data. Fails if the data does
+    /// not start with an RLP list, or the list is truncated.
+    pub fn new(data: &'a [u8]) -> Result<Self, TxDecodeError> {
+        let mut buf = data;
+        let payload_length = match Header::decode(&mut buf) {
+            Ok(Header { list: true, payload_length }) if payload_length <= buf.len() => {
+                payload_length
+            }
+            Ok(Header { list: true, .. }) => {
+                return Err(TxDecodeError::OuterRlp(alloy::rlp::Error::InputTooShort))
+            }
+            Ok(Header { list: false, .. }) => {
+                return Err(TxDecodeError::OuterRlp(alloy::rlp::Error::UnexpectedString))
+            }
+            Err(e) => return Err(TxDecodeError::OuterRlp(e)),
+        };
+        let list_end = data.len() - buf.len() + payload_length;
+        Ok(Self { data, rest: &buf[..payload_length], list_end, index: 0 })
+    }
+
+    /// The offset of the end of the outer list in the block data. Any bytes
+    /// after this offset are not part of the list.
+    pub const fn list_end(&self) -> usize {
+        self.list_end
+    }
+
+    /// The number of trailing bytes after the outer list.
+    pub const fn trailing_bytes(&self) -> usize {
+        self.data.len() - self.list_end
+    }
+
+    /// The index of the next item.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    fn next_item(&mut self) -> Result<TxItem<'a>, TxDecodeFailure> {
+        let index = self.index;
+        let offset = self.list_end - self.rest.len();
+        let fail = |error| TxDecodeFailure { index, offset, error };
+
+        let mut peek = self.rest;
+        let h = Header::decode(&mut peek).map_err(|e| fail(TxDecodeError::OuterRlp(e)))?;
+        if h.payload_length > peek.len() {
+            return Err(fail(TxDecodeError::OuterRlp(alloy::rlp::Error::InputTooShort)));
+        }
+        let (raw, rest) = self.rest.split_at(self.rest.len() - peek.len() + h.payload_length);
+        self.rest = rest;
+        self.index += 1;
+        Ok(TxItem { index, offset, raw })
+    }
+}
+
+impl<'a> Iterator for TxItems<'a> {
+    type Item = Result<TxItem<'a>, TxDecodeFailure>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let res = self.next_item();
+        if res.is_err() {
+            self.rest = &[];
+        }
+        Some(res)
+    }
+}
+
+impl core::iter::FusedIterator for TxItems<'_> {}
+
+/// A single item of an RLP-encoded transaction list, yielded by [`TxItems`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxItem<'a> {
+    /// The index of the item in the list.
+    pub index: usize,
+    /// The byte offset of the item in the block data.
+    pub offset: usize,
+    raw: &'a [u8],
+}
+
+impl<'a> TxItem<'a> {
+    /// The raw RLP encoding of the item.
+    pub const fn raw(&self) -> &'a [u8] {
+        self.raw
+    }
+
+    /// Decode the transaction bytes of the item into `buf`, replacing its
+    /// contents. Reusing `buf` across items avoids allocating per item.
+    ///
+    /// Items are encoded as RLP lists of single-byte integers, so the
+    /// transaction bytes are not contiguous in the block data.
+    pub fn tx_bytes_into(&self, buf: &mut Vec<u8>) -> Result<(), TxDecodeError> {
+        let mut item = self.payload()?;
+        buf.clear();
+        buf.reserve(item.len());
+        while !item.is_empty() {
+            buf.push(u8::decode(&mut item).map_err(TxDecodeError::OuterRlp)?);
+        }
+        Ok(())
+    }
+
+    /// The number of transaction bytes in the item, counted without copying
+    /// them. Fails like [`TxItem::tx_bytes_into`].
+    pub fn tx_len(&self) -> Result<usize, TxDecodeError> {
+        let mut item = self.payload()?;
+        let mut len = 0;
+        while !item.is_empty() {
+            u8::decode(&mut item).map_err(TxDecodeError::OuterRlp)?;
+            len += 1;
+        }
+        Ok(len)
+    }
+
+    fn payload(&self) -> Result<&'a [u8], TxDecodeError> {
+        let mut item = self.raw;
+        let h = Header::decode(&mut item).map_err(TxDecodeError::OuterRlp)?;
+        if !h.list {
+            return Err(TxDecodeError::OuterRlp(alloy::rlp::Error::UnexpectedString));
+        }
+        Ok(item)
+    }
+
+    /// Decode the transaction bytes of the item.
+    pub fn tx_bytes(&self) -> Result<Vec<u8>, TxDecodeError> {
+        let mut buf = Vec::new();
+        self.tx_bytes_into(&mut buf).map(|_| buf)
+    }
+
+    /// Decode the transaction, using `scratch` as a buffer for the transaction
+    /// bytes. Fails if bytes remain after the transaction.
+    pub fn decode<C: Coder>(&self, scratch: &mut Vec<u8>) -> Result<C::Tx, TxDecodeError> {
+        self.tx_bytes_into(scratch)?;
+        let mut buf = scratch.as_slice();
+        let tx = C::try_decode(&mut buf)?;
+        if !buf.is_empty() {
+            return Err(TxDecodeError::TrailingBytes(buf.len()));
+        }
+        Ok(tx)
+    }
+}
+
+/// The length of an item encoding the given transaction bytes.
+pub(crate) fn item_len(tx_bytes: &[u8]) -> usize {
+    let payload_length = item_payload_len(tx_bytes);
+    payload_length + alloy::rlp::length_of_length(payload_length)
+}
+
+/// Bytes of `0x80` and above are encoded with a `0x81` prefix.
+fn item_payload_len(tx_bytes: &[u8]) -> usize {
+    tx_bytes.len() + tx_bytes.iter().filter(|&&b| b >= 0x80).count()
+}
+
+/// Encode concatenated transaction bytes as an RLP list of items, in place.
+/// Transaction `i` ends at `ends[i]` in `buf`.
+///
+/// Items are never shorter than their transaction bytes, so the buffer is
+/// grown to the size of the list, and the items are written back to front.
+/// Each byte is read before its position is written.
+pub(crate) fn encode_items_in_place(buf: &mut Vec<u8>, ends: &[usize]) {
+    let start = |i: usize| i.checked_sub(1).map_or(0, |i| ends[i]);
+    let payload_length = (0..ends.len()).map(|i| item_len(&buf[start(i)..ends[i]])).sum::<usize>();
+    let list = Header { list: true, payload_length };
+    buf.resize(list.length() + payload_length, 0);
+
+    let mut pos = buf.len();
+    for i in (0..ends.len()).rev() {
+        let item = Header { list: true, payload_length: item_payload_len(&buf[start(i)..ends[i]]) };
+        for j in (start(i)..ends[i]).rev() {
+            match buf[j] {
+                0 => {
+                    pos -= 1;
+                    buf[pos] = alloy::rlp::EMPTY_STRING_CODE;
+                }
+                b @ 1..0x80 => {
+                    pos -= 1;
+                    buf[pos] = b;
+                }
+                b => {
+                    pos -= 2;
+                    buf[pos..pos + 2].copy_from_slice(&[0x81, b]);
+                }
+            }
+        }
+        pos -= item.length();
+        item.encode(&mut &mut buf[pos..]);
+    }
+    debug_assert_eq!(pos, list.length());
+    list.encode(&mut &mut buf[..pos]);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::tx;
+    use crate::{encode_txns, Alloy2718Coder};
+    use alloy::eips::eip2718::Encodable2718;
+    use alloy::primitives::keccak256;
+    use alloy::rlp::Encodable;
+
+    #[test]
+    fn items() {
+        let txs = vec![tx(0, 0), tx(1, 10), tx(2, 100)];
+        let data = encode_txns::<Alloy2718Coder>(&txs);
+        let items = TxItems::new(&data).unwrap();
+        assert_eq!(items.trailing_bytes(), 0);
+
+        let mut scratch = Vec::new();
+        let mut offset = data.len() - items.clone().map(|i| i.unwrap().raw().len()).sum::<usize>();
+        for (item, tx) in items.zip(&txs) {
+            let item = item.unwrap();
+            assert_eq!(item.offset, offset);
+            assert_eq!(&data[offset..offset + item.raw().len()], item.raw());
+            offset += item.raw().len();
+
+            assert_eq!(item.tx_bytes().unwrap(), tx.encoded_2718());
+            assert_eq!(item.tx_len().unwrap(), tx.encode_2718_len());
+            assert_eq!(&item.decode::<Alloy2718Coder>(&mut scratch).unwrap(), tx);
+        }
+
+        // raw items can be hashed without decoding
+        let hashes: Vec<_> =
+            TxItems::new(&data).unwrap().map(|i| keccak256(i.unwrap().raw())).collect();
+        assert_eq!(hashes.len(), 3);
+    }
+
+    #[test]
+    fn stops_at_bad_header() {
+        let data = encode_txns::<Alloy2718Coder>(&[tx(0, 10)]);
+        // truncate the item, but fix the list header
+        let mut payload = &data[..];
+        Header::decode(&mut payload).unwrap();
+        let payload = &payload[..payload.len() - 1];
+        let mut fixed = Vec::new();
+        Header { list: true, payload_length: payload.len() }.encode(&mut fixed);
+        fixed.extend_from_slice(payload);
+
+        let mut items = TxItems::new(&fixed).unwrap();
+        let err = items.next().unwrap().unwrap_err();
+        assert_eq!(err.index, 0);
+        assert!(items.next().is_none());
+    }
+
+    #[test]
+    fn item_encoding_matches_rlp() {
+        let txs = vec![vec![], vec![0], vec![0x7f, 0x80, 0xff, 0x00, 0x01], vec![0xab; 1000]];
+        for tx_bytes in &txs {
+            assert_eq!(item_len(tx_bytes), tx_bytes.length());
+        }
+
+        let mut expected = Vec::new();
+        txs.encode(&mut expected);
+
+        let mut out = Vec::new();
+        let mut ends = Vec::new();
+        for tx_bytes in &txs {
+            out.extend_from_slice(tx_bytes);
+            ends.push(out.len());
+        }
+        encode_items_in_place(&mut out, &ends);
+        assert_eq!(out, expected);
+
+        let mut out = Vec::new();
+        encode_items_in_place(&mut out, &[]);
+        assert_eq!(out, [alloy::rlp::EMPTY_LIST_CODE]);
+    }
+
+    #[test]
+    fn encode_txns_matches_rlp() {
+        let txs = vec![tx(0, 0), tx(1, 10), tx(2, 100)];
+
+        // the RLP encoding of the list of envelopes
+        let mut expected = Vec::new();
+        txs.iter().map(Encodable2718::encoded_2718).collect::<Vec<_>>().encode(&mut expected);
+        assert_eq!(encode_txns::<Alloy2718Coder>(&txs), expected);
+    }
+}
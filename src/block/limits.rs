@@ -0,0 +1,312 @@
+use crate::Zenith::BlockHeader as ZenithHeader;
+use crate::{Coder, DecompressionError, TxItems, ZenithBlock, DEFAULT_MAX_DECOMPRESSED_SIZE};
+use alloy::consensus::Transaction;
+use alloy::primitives::keccak256;
+use std::{marker::PhantomData, sync::OnceLock};
+
+/// Limits on the resources used to decode untrusted block data.
+///
+/// The default limits admit any uncompressed block that fits in
+/// [`MAX_BLOBS_PER_BLOCK`] blobs.
+///
+/// [`MAX_BLOBS_PER_BLOCK`]: alloy::eips::eip4844::MAX_BLOBS_PER_BLOCK
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+    /// The maximum length of the block data, both as posted and after
+    /// decompression. Decompression stops once the limit is exceeded. See
+    /// [`Coder::decompress_bounded`].
+    pub max_bytes: usize,
+    /// The maximum number of items in the transaction list.
+    pub max_txns: usize,
+    /// The maximum length of an encoded transaction.
+    pub max_tx_bytes: usize,
+    /// The maximum length of the calldata of a transaction.
+    pub max_calldata: usize,
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_DECOMPRESSED_SIZE,
+            max_txns: 16_384,
+            max_tx_bytes: 1024 * 1024,
+            max_calldata: 1024 * 1024,
+        }
+    }
+}
+
+impl DecodeLimits {
+    /// Limits that admit any block data.
+    pub const fn unlimited() -> Self {
+        Self {
+            max_bytes: usize::MAX,
+            max_txns: usize::MAX,
+            max_tx_bytes: usize::MAX,
+            max_calldata: usize::MAX,
+        }
+    }
+
+    /// Set the maximum length of the block data.
+    pub const fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Set the maximum number of items in the transaction list.
+    pub const fn with_max_txns(mut self, max_txns: usize) -> Self {
+        self.max_txns = max_txns;
+        self
+    }
+
+    /// Set the maximum length of an encoded transaction.
+    pub const fn with_max_tx_bytes(mut self, max_tx_bytes: usize) -> Self {
+        self.max_tx_bytes = max_tx_bytes;
+        self
+    }
+
+    /// Set the maximum length of the calldata of a transaction.
+    pub const fn with_max_calldata(mut self, max_calldata: usize) -> Self {
+        self.max_calldata = max_calldata;
+        self
+    }
+}
+
+/// Errors produced when block data exceeds its [`DecodeLimits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum DecodeLimitError {
+    /// The block data is too long.
+    #[error("block data is {len} bytes, exceeding the limit of {max}")]
+    TooManyBytes {
+        /// The length of the block data.
+        len: usize,
+        /// The limit.
+        max: usize,
+    },
+    /// The block data could not be decompressed.
+    #[error("failed to decompress block data: {0}")]
+    Decompression(DecompressionError),
+    /// The transaction list has too many items.
+    #[error("transaction list exceeds the limit of {max} items")]
+    TooManyTxns {
+        /// The limit.
+        max: usize,
+    },
+    /// An encoded transaction is too long.
+    #[error("transaction {index} is {len} bytes, exceeding the limit of {max}")]
+    TxTooLarge {
+        /// The index of the item in the transaction list.
+        index: usize,
+        /// The length of the encoded transaction.
+        len: usize,
+        /// The limit.
+        max: usize,
+    },
+    /// The calldata of a transaction is too long.
+    #[error("transaction {index} has {len} bytes of calldata, exceeding the limit of {max}")]
+    CalldataTooLarge {
+        /// The index of the item in the transaction list.
+        index: usize,
+        /// The length of the calldata.
+        len: usize,
+        /// The limit.
+        max: usize,
+    },
+}
+
+const fn check_len(len: usize, max: usize) -> Result<(), DecodeLimitError> {
+    if len > max {
+        return Err(DecodeLimitError::TooManyBytes { len, max });
+    }
+    Ok(())
+}
+
+/// Decode transactions, enforcing the given [`DecodeLimits`].
+///
+/// Exceeding any limit aborts decoding with a [`DecodeLimitError`]. Unlike
+/// [`decode_txns`], block data that fails to decompress is an error, reported
+/// as [`DecodeLimitError::Decompression`]. Otherwise this behaves like
+/// [`decode_txns`]: a malformed RLP list produces no transactions, and items
+/// that fail to decode are discarded.
+///
+/// The item count is checked before each item is decoded, so decoding stops as
+/// soon as the limit is exceeded. The byte and calldata limits apply to every
+/// decoded transaction, including those that the coder then filters out, such
+/// as transactions disallowed by a [`PolicyCoder`]. See [`Coder::decode_with`].
+///
+/// [`decode_txns`]: crate::decode_txns
+/// [`PolicyCoder`]: crate::PolicyCoder
+pub fn decode_txns_with_limits<C>(
+    block_data: impl AsRef<[u8]>,
+    limits: &DecodeLimits,
+) -> Result<Vec<C::Tx>, DecodeLimitError>
+where
+    C: Coder,
+    C::Tx: Transaction,
+{
+    let block_data = block_data.as_ref();
+    check_len(block_data.len(), limits.max_bytes)?;
+    let data = C::decompress_bounded(block_data, limits.max_bytes)
+        .map_err(DecodeLimitError::Decompression)?;
+
+    let Ok(items) = TxItems::new(&data) else {
+        return Ok(Vec::new());
+    };
+
+    let mut transactions = Vec::new();
+    let mut scratch = Vec::new();
+    for item in items {
+        let Ok(item) = item else {
+            return Ok(Vec::new());
+        };
+        let index = item.index;
+        if index >= limits.max_txns {
+            return Err(DecodeLimitError::TooManyTxns { max: limits.max_txns });
+        }
+        // Check the length before copying the transaction bytes.
+        let Ok(len) = item.tx_len() else {
+            return Ok(Vec::new());
+        };
+        if len > limits.max_tx_bytes {
+            return Err(DecodeLimitError::TxTooLarge { index, len, max: limits.max_tx_bytes });
+        }
+        if item.tx_bytes_into(&mut scratch).is_err() {
+            return Ok(Vec::new());
+        }
+
+        let tx = C::decode_with(&mut scratch.as_slice(), |tx| {
+            let len = tx.input().len();
+            if len > limits.max_calldata {
+                return Err(DecodeLimitError::CalldataTooLarge {
+                    index,
+                    len,
+                    max: limits.max_calldata,
+                });
+            }
+            Ok(())
+        })?;
+        transactions.extend(tx);
+    }
+    Ok(transactions)
+}
+
+impl<C> ZenithBlock<C>
+where
+    C: Coder,
+    C::Tx: Transaction,
+{
+    /// Decode tx data in the block, enforcing the given [`DecodeLimits`]. See
+    /// [`decode_txns_with_limits`].
+    pub fn from_header_and_data_with_limits(
+        header: ZenithHeader,
+        buf: impl AsRef<[u8]>,
+        limits: &DecodeLimits,
+    ) -> Result<Self, DecodeLimitError> {
+        let b = buf.as_ref();
+        let transactions = decode_txns_with_limits::<C>(b, limits)?;
+        Ok(ZenithBlock {
+            header,
+            transactions,
+            encoded: b.to_owned().into(),
+            block_data_hash: keccak256(b).into(),
+            senders: OnceLock::new(),
+            _pd: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{header, tx};
+    use crate::{decode_txns, encode_txns, Alloy2718Coder};
+    use alloy::eips::eip2718::Encodable2718;
+
+    #[test]
+    fn within_limits() {
+        let txs = vec![tx(0, 10), tx(1, 100)];
+        let data = encode_txns::<Alloy2718Coder>(&txs);
+        let limits = DecodeLimits::default();
+
+        assert_eq!(decode_txns_with_limits::<Alloy2718Coder>(&data, &limits).unwrap(), txs);
+        let block: ZenithBlock =
+            ZenithBlock::from_header_and_data_with_limits(header(), &data, &limits).unwrap();
+        assert_eq!(block.transactions(), txs.as_slice());
+
+        // junk behaves like decode_txns
+        let junk = [0, 1, 2, 3];
+        assert_eq!(
+            decode_txns_with_limits::<Alloy2718Coder>(junk, &limits).unwrap(),
+            decode_txns::<Alloy2718Coder>(junk)
+        );
+    }
+
+    #[test]
+    fn violations() {
+        let txs = vec![tx(0, 10), tx(1, 100), tx(2, 1000)];
+        let data = encode_txns::<Alloy2718Coder>(&txs);
+        let decode =
+            |limits: DecodeLimits| decode_txns_with_limits::<Alloy2718Coder>(&data, &limits);
+        let limits = DecodeLimits::unlimited();
+
+        assert_eq!(
+            decode(limits.with_max_bytes(100)),
+            Err(DecodeLimitError::TooManyBytes { len: data.len(), max: 100 })
+        );
+        assert_eq!(decode(limits.with_max_txns(2)), Err(DecodeLimitError::TooManyTxns { max: 2 }));
+        assert_eq!(
+            decode(limits.with_max_tx_bytes(500)),
+            Err(DecodeLimitError::TxTooLarge { index: 2, len: txs[2].encode_2718_len(), max: 500 })
+        );
+        assert_eq!(
+            decode(limits.with_max_calldata(99)),
+            Err(DecodeLimitError::CalldataTooLarge { index: 1, len: 100, max: 99 })
+        );
+    }
+
+    #[test]
+    fn before_policy() {
+        use crate::{PolicyCoder, TxPolicy};
+        use alloy::consensus::TxType;
+
+        #[derive(Debug, Clone, Copy)]
+        struct OtherChain;
+
+        impl TxPolicy for OtherChain {
+            const ALLOWED_TYPES: &'static [TxType] = &[TxType::Eip1559];
+            const CHAIN_IDS: &'static [u64] = &[1];
+        }
+
+        // the policy drops every transaction, but the limits still apply
+        let txs = vec![tx(0, 10), tx(1, 100)];
+        let data = encode_txns::<Alloy2718Coder>(&txs);
+        let limits = DecodeLimits::unlimited();
+        assert_eq!(
+            decode_txns_with_limits::<PolicyCoder<OtherChain>>(&data, &limits),
+            Ok(Vec::new())
+        );
+        assert_eq!(
+            decode_txns_with_limits::<PolicyCoder<OtherChain>>(
+                &data,
+                &limits.with_max_calldata(99)
+            ),
+            Err(DecodeLimitError::CalldataTooLarge { index: 1, len: 100, max: 99 })
+        );
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn bounded_decompression() {
+        use crate::{DecompressionError, ZstdCoder};
+
+        let txs = vec![tx(0, 10_000)];
+        let data = encode_txns::<ZstdCoder>(&txs);
+        let limits = DecodeLimits::default().with_max_bytes(1024);
+
+        // the compressed data is within the limit, but inflating it is not
+        assert!(data.len() <= 1024);
+        assert_eq!(
+            decode_txns_with_limits::<ZstdCoder>(&data, &limits),
+            Err(DecodeLimitError::Decompression(DecompressionError::TooLarge { max: 1024 }))
+        );
+    }
+}
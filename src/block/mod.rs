@@ -1,10 +1,61 @@
-use std::{marker::PhantomData, sync::OnceLock};
+mod blob;
+pub use blob::{blob_capacity, blobs_required, BlobError};
+
+mod builder;
+pub use builder::{BlockLimitError, RejectedTx, ZenithBlockBuilder, MIN_TRANSACTION_GAS};
+
+mod compress;
+pub use compress::{DecompressionError, DEFAULT_MAX_DECOMPRESSED_SIZE};
+#[cfg(feature = "zstd")]
+pub use compress::{ZstdCoder, ZSTD_LEVEL};
+
+mod decode;
+pub use decode::{decode_txns_strict, DecodeReport, TxDecodeError, TxDecodeFailure};
+
+mod envelope;
+#[cfg(feature = "zstd")]
+pub use envelope::ZSTD_BLOCK_DATA_VERSION;
+pub use envelope::{
+    block_data_version, BlockDataDecoder, DecoderRegistry, UnknownVersion, VersionedCoder,
+    MAX_BLOCK_DATA_VERSION,
+};
+
+mod items;
+pub use items::{TxItem, TxItems};
+
+mod limits;
+pub use limits::{decode_txns_with_limits, DecodeLimitError, DecodeLimits};
+
+mod pack;
+pub use pack::{BlockPacker, PackItem, PackRejection, PackedBlocks, RejectedItem};
+
+mod policy;
+pub use policy::{DisallowedTx, PolicyCoder, PolicyViolation, TxPolicy, TxPolicyConfig};
+
+mod rollup_header;
+pub use rollup_header::{check_header_link, HeaderLinkError, RollupHeader};
+
+mod senders;
+pub use senders::{RecoverSigner, SenderRecoveryError};
+
+mod ser;
+pub use ser::ExpandedBlock;
+
+mod submit;
+pub use submit::{BundleSubmission, SubmitBlockError, SubmittedBlock};
+
+mod trie;
+pub use trie::{TxInclusionProof, TxProofError};
+
+mod validate;
+pub use validate::BlockValidationError;
+
+use std::{borrow::Cow, marker::PhantomData, sync::OnceLock};
 
 use crate::Zenith::BlockHeader as ZenithHeader;
-use alloy::consensus::TxEnvelope;
-use alloy::eips::eip2718::{Decodable2718, Encodable2718};
+use alloy::consensus::{TxEnvelope, TxType};
+use alloy::eips::eip2718::{Decodable2718, Eip2718Error, Encodable2718};
 use alloy::primitives::{keccak256, Address, B256};
-use alloy::rlp::Decodable;
 
 /// Zenith processes normal Ethereum txns.
 pub type ZenithTransaction = TxEnvelope;
@@ -17,10 +68,82 @@ pub trait Coder {
     /// Encode the tx.
     fn encode(t: &Self::Tx) -> Vec<u8>;
 
+    /// Encode the tx, appending it to `out`. Used by [`encode_txns`].
+    ///
+    /// The default implementation delegates to [`Coder::encode`].
+    fn encode_into(t: &Self::Tx, out: &mut Vec<u8>) {
+        out.extend_from_slice(&Self::encode(t));
+    }
+
     /// Decode the tx.
     fn decode(buf: &mut &[u8]) -> Option<Self::Tx>
     where
         Self: Sized;
+
+    /// Decode the tx, returning the reason for failure. Used by
+    /// [`decode_txns_strict`].
+    ///
+    /// The default implementation delegates to [`Coder::decode`], and reports
+    /// all failures as [`TxDecodeError::Rejected`].
+    fn try_decode(buf: &mut &[u8]) -> Result<Self::Tx, TxDecodeError>
+    where
+        Self: Sized,
+    {
+        Self::decode(buf).ok_or(TxDecodeError::Rejected)
+    }
+
+    /// Decode the tx, passing it to `check` before the coder filters it.
+    /// Used by [`decode_txns_with_limits`], so that limits apply to every
+    /// transaction in the block data, including those dropped by a
+    /// [`PolicyCoder`].
+    ///
+    /// The default implementation passes the output of [`Coder::decode`] to
+    /// `check`. Coders that filter decoded transactions should call `check`
+    /// before filtering.
+    fn decode_with<E>(
+        buf: &mut &[u8],
+        check: impl FnOnce(&Self::Tx) -> Result<(), E>,
+    ) -> Result<Option<Self::Tx>, E>
+    where
+        Self: Sized,
+    {
+        let tx = Self::decode(buf);
+        if let Some(tx) = &tx {
+            check(tx)?;
+        }
+        Ok(tx)
+    }
+
+    /// Transform the RLP-encoded transaction list into block data. Used by
+    /// [`encode_txns`].
+    ///
+    /// The default implementation returns the data unchanged.
+    fn compress(data: Vec<u8>) -> Vec<u8> {
+        data
+    }
+
+    /// Recover the RLP-encoded transaction list from block data. This is the
+    /// inverse of [`Coder::compress`], and is used by [`decode_txns`] and
+    /// [`decode_txns_strict`].
+    ///
+    /// The default implementation returns the data unchanged.
+    fn decompress(data: &[u8]) -> Result<Cow<'_, [u8]>, DecompressionError> {
+        Ok(Cow::Borrowed(data))
+    }
+
+    /// Like [`Coder::decompress`], but fails with
+    /// [`DecompressionError::TooLarge`] if the output would exceed `max`
+    /// bytes. Used by [`decode_txns_with_limits`].
+    ///
+    /// The default implementation decompresses fully, then checks the
+    /// length. Coders that inflate data should stop once `max` is exceeded.
+    fn decompress_bounded(data: &[u8], max: usize) -> Result<Cow<'_, [u8]>, DecompressionError> {
+        let out = Self::decompress(data)?;
+        if out.len() > max {
+            return Err(DecompressionError::TooLarge { max });
+        }
+        Ok(out)
+    }
 }
 
 /// Coder for [`encode_txns`] and [`decode_txns`] that operates on
@@ -35,15 +158,41 @@ impl Coder for Alloy2718Coder {
         t.encoded_2718()
     }
 
+    fn encode_into(t: &ZenithTransaction, out: &mut Vec<u8>) {
+        t.encode_2718(out)
+    }
+
     fn decode(buf: &mut &[u8]) -> Option<ZenithTransaction>
     where
         Self: Sized,
     {
         ZenithTransaction::decode_2718(buf).ok()
     }
+
+    fn try_decode(buf: &mut &[u8]) -> Result<ZenithTransaction, TxDecodeError>
+    where
+        Self: Sized,
+    {
+        // Typed envelopes start with a type byte below 0x80. Alloy reports an
+        // unknown type as a generic RLP error, so we check it here.
+        if let Some(&ty) = buf.first() {
+            if ty < 0x80 && TxType::try_from(ty).is_err() {
+                return Err(TxDecodeError::UnsupportedType(ty));
+            }
+        }
+        ZenithTransaction::decode_2718(buf).map_err(|e| match e {
+            Eip2718Error::UnexpectedType(ty) => TxDecodeError::UnsupportedType(ty),
+            Eip2718Error::RlpError(e) => TxDecodeError::Inner2718(e),
+            _ => TxDecodeError::Rejected,
+        })
+    }
 }
 
 /// A Zenith block is just a list of transactions.
+///
+/// The block accepts any transaction. Use a [`ZenithBlockBuilder`] to refuse
+/// transactions that the coder would not decode, such as those disallowed by
+/// a [`PolicyCoder`].
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ZenithBlock<C: Coder = Alloy2718Coder> {
     /// The zenith block header, which may be extracted from a
@@ -56,6 +205,7 @@ pub struct ZenithBlock<C: Coder = Alloy2718Coder> {
     // memoization fields
     encoded: OnceLock<Vec<u8>>,
     block_data_hash: OnceLock<B256>,
+    senders: OnceLock<Vec<Address>>,
 
     /// The coder
     _pd: std::marker::PhantomData<C>,
@@ -72,6 +222,7 @@ where
             transactions,
             encoded: OnceLock::new(),
             block_data_hash: OnceLock::new(),
+            senders: OnceLock::new(),
             _pd: PhantomData,
         }
     }
@@ -93,6 +244,7 @@ where
             transactions,
             encoded: b.to_owned().into(),
             block_data_hash: h.into(),
+            senders: OnceLock::new(),
             _pd: PhantomData,
         }
     }
@@ -160,6 +312,7 @@ where
     fn unseal(&mut self) {
         self.encoded.take();
         self.block_data_hash.take();
+        self.senders.take();
     }
 
     /// Get the chain ID of the block (discarding high bytes).
@@ -190,34 +343,59 @@ where
 ///
 /// The function is generic over the coder type, which is used to decode the
 /// transactions. This allows for different transaction types to be decoded
-/// using different coders.
+/// using different coders. The block data is first passed through
+/// [`Coder::decompress`]. If that fails, or the RLP list is malformed, no
+/// transactions are returned. Items that fail to decode are discarded.
+///
+/// Items are decoded lazily via [`TxItems`], sharing one buffer for the
+/// transaction bytes.
 pub fn decode_txns<C>(block_data: impl AsRef<[u8]>) -> Vec<C::Tx>
 where
     C: Coder,
 {
-    let mut bd = block_data.as_ref();
-
-    Vec::<Vec<u8>>::decode(&mut bd)
-        .map(|rlp| rlp.into_iter().flat_map(|buf| C::decode(&mut buf.as_slice())).collect())
-        .ok()
-        .unwrap_or_default()
+    let Ok(data) = C::decompress(block_data.as_ref()) else {
+        return Vec::new();
+    };
+    let Ok(items) = TxItems::new(&data) else {
+        return Vec::new();
+    };
+
+    let mut transactions = Vec::new();
+    let mut scratch = Vec::new();
+    for item in items {
+        let Ok(item) = item else {
+            return Vec::new();
+        };
+        if item.tx_bytes_into(&mut scratch).is_err() {
+            return Vec::new();
+        }
+        transactions.extend(C::decode(&mut scratch.as_slice()));
+    }
+    transactions
 }
 
 /// Encode a set of transactions into a single RLP-encoded buffer.
 ///
 /// The function is generic over the coder type, which is used to encode the
 /// transactions. This allows for different transaction types to be encoded
-/// using different encodings.
+/// using different encodings. The RLP list is then passed through
+/// [`Coder::compress`].
+///
+/// Transactions are encoded directly into the output buffer, which is then
+/// expanded into the RLP list in place, so that no second buffer is needed.
 pub fn encode_txns<'a, C>(transactions: impl IntoIterator<Item = &'a C::Tx>) -> Vec<u8>
 where
     C: Coder,
     C::Tx: 'a,
 {
-    let encoded_txns = transactions.into_iter().map(|tx| C::encode(tx)).collect::<Vec<Vec<u8>>>();
-
     let mut buf = Vec::new();
-    alloy::rlp::Encodable::encode(&encoded_txns, &mut buf);
-    buf
+    let mut ends = Vec::new();
+    for tx in transactions {
+        C::encode_into(tx, &mut buf);
+        ends.push(buf.len());
+    }
+    items::encode_items_in_place(&mut buf, &ends);
+    C::compress(buf)
 }
 
 #[cfg(test)]
@@ -0,0 +1,352 @@
+use crate::Zenith::BlockHeader as ZenithHeader;
+use crate::{blob_capacity, Alloy2718Coder, BlockLimitError, Coder, RecoverSigner, RejectedTx};
+use crate::{ZenithBlock, ZenithBlockBuilder};
+use alloy::consensus::Transaction;
+use alloy::eips::eip4844::MAX_BLOBS_PER_BLOCK;
+use alloy::primitives::{Address, U256};
+use std::{collections::HashSet, marker::PhantomData};
+
+/// An item in a transaction stream to be packed by a [`BlockPacker`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackItem<T> {
+    /// A single transaction.
+    Tx(T),
+    /// An atomic bundle of transactions. Either all of the transactions are
+    /// included in the same block, in order, or none are.
+    Bundle(Vec<T>),
+}
+
+impl<T> From<T> for PackItem<T> {
+    fn from(tx: T) -> Self {
+        Self::Tx(tx)
+    }
+}
+
+impl<T> PackItem<T> {
+    /// The transactions in the item.
+    pub fn transactions(&self) -> &[T] {
+        match self {
+            Self::Tx(tx) => std::slice::from_ref(tx),
+            Self::Bundle(txs) => txs,
+        }
+    }
+}
+
+/// Reasons a [`BlockPacker`] may reject an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum PackRejection {
+    /// The item does not fit in an empty block.
+    #[error(transparent)]
+    Limit(#[from] BlockLimitError),
+    /// The sender of a transaction in the item could not be recovered.
+    #[error("invalid signature")]
+    InvalidSignature,
+    /// The item is a bundle with no transactions.
+    #[error("empty bundle")]
+    EmptyBundle,
+    /// An earlier item from this sender was rejected, so the nonces of this
+    /// item cannot be valid.
+    #[error("earlier item from sender {0} was rejected")]
+    SenderRejected(Address),
+}
+
+/// An item rejected by a [`BlockPacker`], along with the reason.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedItem<T> {
+    /// The rejected item.
+    pub item: PackItem<T>,
+    /// The reason for rejection.
+    pub reason: PackRejection,
+}
+
+/// The output of [`BlockPacker::pack`].
+#[derive(Debug, Clone)]
+pub struct PackedBlocks<C: Coder> {
+    /// The packed blocks, in order.
+    pub blocks: Vec<ZenithBlock<C>>,
+    /// Items that can never be included.
+    pub rejected: Vec<RejectedItem<C::Tx>>,
+    /// Items left over after the maximum number of blocks was reached, in
+    /// their original order.
+    pub remaining: Vec<PackItem<C::Tx>>,
+}
+
+/// Packs an ordered stream of transactions and atomic bundles into
+/// consecutive [`ZenithBlock`]s.
+///
+/// Each block is filled by a [`ZenithBlockBuilder`], so it respects the
+/// header's chain id and gas limit, and the byte budget. Items that do not
+/// fit in a block are deferred to the next block. Later items from any
+/// sender with a deferred item are deferred as well, so each sender's
+/// transactions stay in their original order. Items that do not fit even
+/// in an empty block, or that the builder refuses for any reason other than
+/// capacity, such as a wrong chain id, are rejected, along with all later
+/// items from the same senders. See [`BlockLimitError::is_capacity`].
+///
+/// Consecutive blocks are built from the same header, with the host block
+/// number incremented for each block. Every block keeps the header's
+/// `blockDataHash`, which should be set by the caller once the block data is
+/// final. See [`ZenithBlock::block_data_hash`].
+#[derive(Debug, Clone, Copy)]
+pub struct BlockPacker<C: Coder = Alloy2718Coder> {
+    header: ZenithHeader,
+    max_bytes: usize,
+    max_blocks: usize,
+    _pd: PhantomData<C>,
+}
+
+impl<C> BlockPacker<C>
+where
+    C: Coder,
+    C::Tx: Transaction + RecoverSigner,
+{
+    /// Create a new packer. The first block is built with the given header.
+    /// By default, the byte budget is the capacity of
+    /// [`MAX_BLOBS_PER_BLOCK`] blobs and the number of blocks is unlimited.
+    pub const fn new(header: ZenithHeader) -> Self {
+        Self {
+            header,
+            max_bytes: blob_capacity(MAX_BLOBS_PER_BLOCK),
+            max_blocks: usize::MAX,
+            _pd: PhantomData,
+        }
+    }
+
+    /// Set the byte budget for the encoded block data of each block.
+    pub const fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Set the byte budget of each block to the capacity of `num_blobs`
+    /// blobs.
+    pub const fn with_max_blobs(self, num_blobs: usize) -> Self {
+        self.with_max_bytes(blob_capacity(num_blobs))
+    }
+
+    /// Set the maximum number of blocks to produce.
+    pub const fn with_max_blocks(mut self, max_blocks: usize) -> Self {
+        self.max_blocks = max_blocks;
+        self
+    }
+
+    fn builder(&self, n: usize) -> ZenithBlockBuilder<C> {
+        let mut header = self.header;
+        header.hostBlockNumber += U256::from(n);
+        ZenithBlockBuilder::new(header).with_max_bytes(self.max_bytes)
+    }
+
+    /// Pack the items into blocks.
+    pub fn pack(&self, items: impl IntoIterator<Item = PackItem<C::Tx>>) -> PackedBlocks<C> {
+        let mut blocks = Vec::new();
+        let mut rejected = Vec::new();
+        let mut rejected_senders = HashSet::new();
+
+        let mut queue = Vec::new();
+        for item in items {
+            if item.transactions().is_empty() {
+                rejected.push(RejectedItem { item, reason: PackRejection::EmptyBundle });
+                continue;
+            }
+            match item
+                .transactions()
+                .iter()
+                .map(RecoverSigner::recover_signer)
+                .collect::<Result<Vec<_>, _>>()
+            {
+                Ok(senders) => queue.push((item, senders)),
+                Err(_) => {
+                    rejected.push(RejectedItem { item, reason: PackRejection::InvalidSignature })
+                }
+            }
+        }
+
+        while !queue.is_empty() && blocks.len() < self.max_blocks {
+            let mut builder = self.builder(blocks.len());
+            let mut deferred_senders = HashSet::new();
+            let mut deferred = Vec::new();
+
+            for (item, senders) in queue {
+                if let Some(&sender) = senders.iter().find(|s| rejected_senders.contains(*s)) {
+                    rejected
+                        .push(RejectedItem { item, reason: PackRejection::SenderRejected(sender) });
+                    continue;
+                }
+                // An empty builder is never treated as full, so that items
+                // which cannot fit in any block are rejected rather than
+                // deferred forever.
+                let was_empty = builder.block().transactions().is_empty();
+                if (!was_empty && builder.is_full())
+                    || senders.iter().any(|s| deferred_senders.contains(s))
+                {
+                    deferred_senders.extend(senders.iter().copied());
+                    deferred.push((item, senders));
+                    continue;
+                }
+
+                let res = match item {
+                    PackItem::Tx(tx) => builder
+                        .push_transaction(tx)
+                        .map_err(|RejectedTx { tx, reason }| (PackItem::Tx(tx), reason)),
+                    PackItem::Bundle(txs) => builder
+                        .push_bundle(txs)
+                        .map_err(|RejectedTx { tx, reason }| (PackItem::Bundle(tx), reason)),
+                };
+                match res {
+                    Ok(()) => {}
+                    Err((item, reason)) if was_empty || !reason.is_capacity() => {
+                        rejected_senders.extend(senders.iter().copied());
+                        rejected.push(RejectedItem { item, reason: reason.into() });
+                    }
+                    Err((item, _)) => {
+                        deferred_senders.extend(senders.iter().copied());
+                        deferred.push((item, senders));
+                    }
+                }
+            }
+
+            if !builder.block().transactions().is_empty() {
+                blocks.push(builder.build());
+            }
+            queue = deferred;
+        }
+
+        let remaining = queue.into_iter().map(|(item, _)| item).collect();
+        PackedBlocks { blocks, rejected, remaining }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{header, signed_tx as tx, signer};
+
+    fn packer(gas_limit: u64) -> BlockPacker {
+        let mut h = header();
+        h.gasLimit = U256::from(gas_limit);
+        BlockPacker::new(h)
+    }
+
+    fn nonces(block: &ZenithBlock) -> Vec<u64> {
+        block.transactions().iter().map(|tx| tx.nonce()).collect()
+    }
+
+    #[test]
+    fn consecutive_blocks() {
+        let a = signer(1);
+        let packed = packer(50_000).pack((0..5).map(|n| tx(&a, n, 21_000).into()));
+
+        assert_eq!(packed.blocks.len(), 3);
+        assert_eq!(nonces(&packed.blocks[0]), [0, 1]);
+        assert_eq!(nonces(&packed.blocks[2]), [4]);
+        assert_eq!(packed.blocks[2].block_height(), 102);
+        assert!(packed.rejected.is_empty() && packed.remaining.is_empty());
+    }
+
+    #[test]
+    fn sender_order() {
+        let (a, b) = (signer(1), signer(2));
+        let items = vec![
+            tx(&a, 0, 30_000).into(),
+            // does not fit after a0, so a2 must wait for it
+            tx(&a, 1, 35_000).into(),
+            tx(&a, 2, 21_000).into(),
+            tx(&b, 0, 21_000).into(),
+        ];
+        let packed = packer(60_000).pack(items);
+
+        let senders = |block: &ZenithBlock| block.senders().unwrap().to_vec();
+        assert_eq!(senders(&packed.blocks[0]), [a.address(), b.address()]);
+        assert_eq!(nonces(&packed.blocks[1]), [1, 2]);
+    }
+
+    #[test]
+    fn atomic_bundles() {
+        let (a, b) = (signer(1), signer(2));
+        let items = vec![
+            tx(&a, 0, 30_000).into(),
+            PackItem::Bundle(vec![tx(&b, 0, 21_000), tx(&b, 1, 21_000)]),
+            tx(&a, 1, 21_000).into(),
+        ];
+        let packed = packer(60_000).pack(items);
+
+        assert_eq!(nonces(&packed.blocks[0]), [0, 1]);
+        assert_eq!(packed.blocks[0].senders().unwrap(), [a.address(), a.address()]);
+        assert_eq!(packed.blocks[1].senders().unwrap(), [b.address(), b.address()]);
+    }
+
+    #[test]
+    fn rejections_and_remaining() {
+        let (a, b) = (signer(1), signer(2));
+        let items = vec![
+            tx(&a, 0, 100_000).into(),
+            tx(&b, 0, 21_000).into(),
+            tx(&a, 1, 21_000).into(),
+            tx(&b, 1, 21_000).into(),
+        ];
+        let packed = packer(30_000).with_max_blocks(1).pack(items);
+
+        assert_eq!(packed.blocks.len(), 1);
+        assert_eq!(
+            packed.rejected[0].reason,
+            PackRejection::Limit(BlockLimitError::GasLimit { needed: 100_000, remaining: 30_000 })
+        );
+        assert_eq!(packed.rejected[1].reason, PackRejection::SenderRejected(a.address()));
+        assert_eq!(packed.remaining, vec![tx(&b, 1, 21_000).into()]);
+    }
+
+    #[test]
+    fn unfillable_blocks() {
+        let a = signer(1);
+
+        // no transaction fits in the gas limit
+        let packed = packer(20_000).pack(vec![tx(&a, 0, 21_000).into()]);
+        assert!(packed.blocks.is_empty() && packed.remaining.is_empty());
+        assert_eq!(
+            packed.rejected[0].reason,
+            PackRejection::Limit(BlockLimitError::GasLimit { needed: 21_000, remaining: 20_000 })
+        );
+
+        // no transaction fits in the byte budget
+        let packed = packer(50_000).with_max_bytes(0).pack(vec![tx(&a, 0, 21_000).into()]);
+        assert!(packed.blocks.is_empty() && packed.remaining.is_empty());
+        assert!(matches!(packed.rejected[0].reason, PackRejection::Limit(_)));
+    }
+
+    #[test]
+    fn non_capacity_rejections() {
+        use alloy::consensus::{SignableTransaction, TxEip1559};
+        use alloy::signers::SignerSync;
+
+        let (a, b) = (signer(1), signer(2));
+        let wrong_chain = {
+            let tx = TxEip1559 { chain_id: 1, gas_limit: 21_000, ..Default::default() };
+            let sig = b.sign_hash_sync(&tx.signature_hash()).unwrap();
+            crate::ZenithTransaction::from(tx.into_signed(sig))
+        };
+        let items = vec![
+            tx(&a, 0, 21_000).into(),
+            wrong_chain.into(),
+            tx(&b, 1, 21_000).into(),
+            tx(&a, 1, 21_000).into(),
+        ];
+        let packed = packer(50_000).with_max_blocks(1).pack(items);
+
+        // rejected at once, rather than deferred to a block that is never
+        // built
+        assert_eq!(
+            packed.rejected[0].reason,
+            PackRejection::Limit(BlockLimitError::WrongChainId { expected: 17, got: Some(1) })
+        );
+        assert_eq!(packed.rejected[1].reason, PackRejection::SenderRejected(b.address()));
+        assert_eq!(nonces(&packed.blocks[0]), [0, 1]);
+        assert!(packed.remaining.is_empty());
+    }
+
+    #[test]
+    fn empty_bundle() {
+        let packed = packer(50_000).pack(vec![PackItem::Bundle(vec![])]);
+        assert!(packed.blocks.is_empty() && packed.remaining.is_empty());
+        assert_eq!(packed.rejected[0].reason, PackRejection::EmptyBundle);
+    }
+}
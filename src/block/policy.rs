@@ -0,0 +1,324 @@
+use crate::{Alloy2718Coder, Coder, DecodeReport, DecompressionError, TxDecodeError};
+use alloy::consensus::{Transaction, TxType};
+use std::{borrow::Cow, marker::PhantomData};
+
+/// Reasons a transaction may be disallowed by a [`TxPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum PolicyViolation {
+    /// The transaction type is not allowed.
+    #[error("transaction type {0:#04x} is not allowed")]
+    TxType(u8),
+    /// The chain id is not allowed, or the transaction has no chain id.
+    #[error("chain id {0:?} is not allowed")]
+    ChainId(Option<u64>),
+}
+
+/// A static policy on the transactions allowed in block data, used by
+/// [`PolicyCoder`]. For a policy built at runtime, see [`TxPolicyConfig`].
+///
+/// ```
+/// # use alloy::consensus::TxType;
+/// # use zenith_types::{PolicyCoder, TxPolicy};
+/// #[derive(Debug, Clone, Copy)]
+/// struct NoBlobs;
+///
+/// impl TxPolicy for NoBlobs {
+///     const ALLOWED_TYPES: &'static [TxType] =
+///         &[TxType::Legacy, TxType::Eip2930, TxType::Eip1559];
+///     const CHAIN_IDS: &'static [u64] = &[17];
+/// }
+///
+/// type NoBlobsCoder = PolicyCoder<NoBlobs>;
+/// ```
+pub trait TxPolicy {
+    /// The allowed transaction types.
+    const ALLOWED_TYPES: &'static [TxType];
+
+    /// The allowed chain ids. If empty, any chain id is allowed, including
+    /// none.
+    const CHAIN_IDS: &'static [u64];
+
+    /// Check a transaction against the policy.
+    fn check<T: Transaction>(tx: &T) -> Result<(), PolicyViolation> {
+        check_tx(Self::ALLOWED_TYPES, Self::CHAIN_IDS, tx)
+    }
+}
+
+fn check_tx<T: Transaction>(
+    allowed_types: &[TxType],
+    chain_ids: &[u64],
+    tx: &T,
+) -> Result<(), PolicyViolation> {
+    let ty = tx.ty();
+    if !allowed_types.iter().any(|&allowed| allowed as u8 == ty) {
+        return Err(PolicyViolation::TxType(ty));
+    }
+    let chain_id = tx.chain_id();
+    if !chain_ids.is_empty() && !chain_id.is_some_and(|id| chain_ids.contains(&id)) {
+        return Err(PolicyViolation::ChainId(chain_id));
+    }
+    Ok(())
+}
+
+/// A policy on the transactions allowed in block data, built at runtime,
+/// for example from [`NetworkConstants`].
+///
+/// Unlike a [`TxPolicy`], it is not part of a [`Coder`]. It is applied by
+/// - [`TxPolicyConfig::encode_txns`]
+/// - [`TxPolicyConfig::decode_txns_strict`]
+/// - [`ZenithBlockBuilder::with_policy`]
+///
+/// [`NetworkConstants`]: crate::NetworkConstants
+/// [`ZenithBlockBuilder::with_policy`]: crate::ZenithBlockBuilder::with_policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxPolicyConfig {
+    /// The allowed transaction types.
+    pub allowed_types: Vec<TxType>,
+    /// The allowed chain ids. If empty, any chain id is allowed, including
+    /// none.
+    pub chain_ids: Vec<u64>,
+}
+
+impl TxPolicyConfig {
+    /// Create a policy allowing the given transaction types and chain ids.
+    pub fn new(
+        allowed_types: impl IntoIterator<Item = TxType>,
+        chain_ids: impl IntoIterator<Item = u64>,
+    ) -> Self {
+        Self {
+            allowed_types: allowed_types.into_iter().collect(),
+            chain_ids: chain_ids.into_iter().collect(),
+        }
+    }
+
+    /// Create a policy with the same rules as the static policy `P`.
+    pub fn of<P: TxPolicy>() -> Self {
+        Self::new(P::ALLOWED_TYPES.iter().copied(), P::CHAIN_IDS.iter().copied())
+    }
+
+    /// Check a transaction against the policy.
+    pub fn check<T: Transaction>(&self, tx: &T) -> Result<(), PolicyViolation> {
+        check_tx(&self.allowed_types, &self.chain_ids, tx)
+    }
+
+    /// Encode a set of transactions like [`encode_txns`], refusing them if
+    /// any is disallowed by this policy. Returns the first disallowed
+    /// transaction.
+    ///
+    /// [`encode_txns`]: crate::encode_txns
+    pub fn encode_txns<C>(&self, transactions: &[C::Tx]) -> Result<Vec<u8>, DisallowedTx>
+    where
+        C: Coder,
+        C::Tx: Transaction,
+    {
+        for (index, tx) in transactions.iter().enumerate() {
+            self.check(tx).map_err(|violation| DisallowedTx { index, violation })?;
+        }
+        Ok(crate::encode_txns::<C>(transactions))
+    }
+
+    /// Decode transactions like [`decode_txns_strict`], also reporting the
+    /// transactions disallowed by this policy as [`TxDecodeError::Policy`].
+    ///
+    /// [`decode_txns_strict`]: crate::decode_txns_strict
+    pub fn decode_txns_strict<C>(&self, block_data: impl AsRef<[u8]>) -> DecodeReport<C::Tx>
+    where
+        C: Coder,
+        C::Tx: Transaction,
+    {
+        super::decode::decode_txns_strict_with::<C>(block_data.as_ref(), |tx| self.check(tx))
+    }
+}
+
+/// Coder that enforces a [`TxPolicy`] when decoding the transactions of an
+/// inner coder.
+///
+/// Disallowed transactions are:
+/// - dropped by [`decode_txns`]
+/// - reported as [`TxDecodeError::Policy`] by [`decode_txns_strict`]
+/// - refused by [`ZenithBlockBuilder`] with [`BlockLimitError::Policy`]
+///
+/// Encoding does not check the policy, so the block data always commits to
+/// every transaction of a [`ZenithBlock`]. Use a [`ZenithBlockBuilder`], or
+/// [`TxPolicyConfig::encode_txns`] with [`TxPolicyConfig::of`], to refuse
+/// disallowed transactions before encoding.
+///
+/// [`ZenithBlock`]: crate::ZenithBlock
+/// [`decode_txns`]: crate::decode_txns
+/// [`decode_txns_strict`]: crate::decode_txns_strict
+/// [`ZenithBlockBuilder`]: crate::ZenithBlockBuilder
+/// [`BlockLimitError::Policy`]: crate::BlockLimitError::Policy
+#[derive(Debug, Clone, Copy)]
+pub struct PolicyCoder<P, C = Alloy2718Coder>(PhantomData<(P, C)>);
+
+impl<P, C> Coder for PolicyCoder<P, C>
+where
+    P: TxPolicy,
+    C: Coder,
+    C::Tx: Transaction,
+{
+    type Tx = C::Tx;
+
+    fn encode(t: &Self::Tx) -> Vec<u8> {
+        C::encode(t)
+    }
+
+    fn encode_into(t: &Self::Tx, out: &mut Vec<u8>) {
+        C::encode_into(t, out)
+    }
+
+    fn decode(buf: &mut &[u8]) -> Option<Self::Tx>
+    where
+        Self: Sized,
+    {
+        C::decode(buf).filter(|tx| P::check(tx).is_ok())
+    }
+
+    fn try_decode(buf: &mut &[u8]) -> Result<Self::Tx, TxDecodeError>
+    where
+        Self: Sized,
+    {
+        let tx = C::try_decode(buf)?;
+        P::check(&tx).map_err(TxDecodeError::Policy)?;
+        Ok(tx)
+    }
+
+    fn decode_with<E>(
+        buf: &mut &[u8],
+        check: impl FnOnce(&Self::Tx) -> Result<(), E>,
+    ) -> Result<Option<Self::Tx>, E>
+    where
+        Self: Sized,
+    {
+        Ok(C::decode_with(buf, check)?.filter(|tx| P::check(tx).is_ok()))
+    }
+
+    fn compress(data: Vec<u8>) -> Vec<u8> {
+        C::compress(data)
+    }
+
+    fn decompress(data: &[u8]) -> Result<Cow<'_, [u8]>, DecompressionError> {
+        C::decompress(data)
+    }
+
+    fn decompress_bounded(data: &[u8], max: usize) -> Result<Cow<'_, [u8]>, DecompressionError> {
+        C::decompress_bounded(data, max)
+    }
+}
+
+/// A transaction refused by [`TxPolicyConfig::encode_txns`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("transaction {index} is disallowed: {violation}")]
+pub struct DisallowedTx {
+    /// The index of the transaction in the input.
+    pub index: usize,
+    /// Why the transaction is disallowed.
+    pub violation: PolicyViolation,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{header, tx};
+    use crate::ZenithTransaction;
+    use crate::{
+        decode_txns, decode_txns_strict, encode_txns, BlockLimitError, ZenithBlockBuilder,
+    };
+    use alloy::consensus::{SignableTransaction, TxEip2930};
+    use alloy::primitives::PrimitiveSignature;
+
+    #[derive(Debug, Clone, Copy)]
+    struct Only1559;
+
+    impl TxPolicy for Only1559 {
+        const ALLOWED_TYPES: &'static [TxType] = &[TxType::Eip1559];
+        const CHAIN_IDS: &'static [u64] = &[17];
+    }
+
+    type Strict = PolicyCoder<Only1559>;
+
+    fn eip2930() -> ZenithTransaction {
+        TxEip2930 { chain_id: 17, gas_limit: 21_000, ..Default::default() }
+            .into_signed(PrimitiveSignature::test_signature())
+            .into()
+    }
+
+    fn wrong_chain() -> ZenithTransaction {
+        let ZenithTransaction::Eip1559(signed) = tx(0, 0) else { unreachable!() };
+        let mut inner = signed.strip_signature();
+        inner.chain_id = 1;
+        inner.into_signed(PrimitiveSignature::test_signature()).into()
+    }
+
+    #[test]
+    fn encode() {
+        let txs = vec![tx(0, 0), eip2930(), wrong_chain(), tx(1, 0)];
+        let allowed = vec![tx(0, 0), tx(1, 0)];
+        let policy = TxPolicyConfig::of::<Only1559>();
+
+        assert_eq!(
+            policy.encode_txns::<Alloy2718Coder>(&txs),
+            Err(DisallowedTx { index: 1, violation: PolicyViolation::TxType(1) })
+        );
+        assert_eq!(
+            policy.encode_txns::<Alloy2718Coder>(&allowed).unwrap(),
+            encode_txns::<Alloy2718Coder>(&allowed)
+        );
+
+        // the coder encodes every transaction, and drops disallowed ones when
+        // decoding
+        let data = encode_txns::<Strict>(&txs);
+        assert_eq!(data, encode_txns::<Alloy2718Coder>(&txs));
+        assert_eq!(decode_txns::<Strict>(&data), allowed);
+    }
+
+    #[test]
+    fn decode() {
+        let txs = vec![tx(0, 0), eip2930(), wrong_chain()];
+        let data = encode_txns::<Alloy2718Coder>(&txs);
+
+        assert_eq!(decode_txns::<Strict>(&data), vec![tx(0, 0)]);
+
+        let report = decode_txns_strict::<Strict>(&data);
+        assert_eq!(report.transactions, vec![tx(0, 0)]);
+        assert_eq!(report.failures[0].error, TxDecodeError::Policy(PolicyViolation::TxType(1)));
+        assert_eq!(
+            report.failures[1].error,
+            TxDecodeError::Policy(PolicyViolation::ChainId(Some(1)))
+        );
+    }
+
+    #[test]
+    fn builder() {
+        let builder = ZenithBlockBuilder::<Strict>::new(header());
+        assert_eq!(
+            builder.check(&eip2930()),
+            Err(BlockLimitError::Policy(PolicyViolation::TxType(1)))
+        );
+
+        let builder = ZenithBlockBuilder::<Alloy2718Coder>::new(header())
+            .with_policy(TxPolicyConfig::of::<Only1559>());
+        assert_eq!(
+            builder.check(&eip2930()),
+            Err(BlockLimitError::Policy(PolicyViolation::TxType(1)))
+        );
+        builder.check(&tx(0, 0)).unwrap();
+    }
+
+    #[test]
+    fn runtime_policy() {
+        let policy = TxPolicyConfig::new([TxType::Eip1559], [17]);
+        assert_eq!(policy, TxPolicyConfig::of::<Only1559>());
+
+        let txs = vec![tx(0, 0), eip2930(), wrong_chain()];
+        let data = encode_txns::<Alloy2718Coder>(&txs);
+        assert_eq!(
+            policy.decode_txns_strict::<Alloy2718Coder>(&data),
+            decode_txns_strict::<Strict>(&data)
+        );
+
+        // an empty chain id list allows any chain
+        let any_chain = TxPolicyConfig::new([TxType::Eip1559], []);
+        any_chain.check(&wrong_chain()).unwrap();
+    }
+}
@@ -0,0 +1,224 @@
+use crate::{Coder, ZenithBlock};
+use alloy::consensus::Header;
+use alloy::primitives::{Address, B256};
+use serde::{Deserialize, Serialize};
+
+/// Errors produced when a rollup header does not link to its parent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum HeaderLinkError {
+    /// The block number is not one greater than the parent's.
+    #[error("block number mismatch: expected {expected}, got {got}")]
+    Number {
+        /// The number following the parent's.
+        expected: u64,
+        /// The block number.
+        got: u64,
+    },
+    /// The parent number is `u64::MAX`, so no block can follow it.
+    #[error("parent number {parent} has no successor")]
+    NumberOverflow {
+        /// The number of the parent.
+        parent: u64,
+    },
+    /// The parent hash is not the hash of the parent.
+    #[error("parent hash mismatch: expected {expected}, got {got}")]
+    ParentHash {
+        /// The hash of the parent.
+        expected: B256,
+        /// The parent hash of the header.
+        got: B256,
+    },
+    /// The timestamp is not later than the parent's.
+    #[error("timestamp {got} is not after parent timestamp {parent}")]
+    Timestamp {
+        /// The timestamp of the parent.
+        parent: u64,
+        /// The timestamp of the header.
+        got: u64,
+    },
+}
+
+/// The rollup header fields derived from a [`ZenithBlock`] and its parent.
+///
+/// Fields that depend on execution, such as the state root and gas used, are
+/// not included. Converting to a [`Header`] leaves them at their defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollupHeader {
+    /// The rollup block number, one greater than the parent's.
+    pub number: u64,
+    /// The hash of the parent rollup header.
+    pub parent_hash: B256,
+    /// The timestamp of the host block in which the block was submitted.
+    pub timestamp: u64,
+    /// The gas limit, from the Zenith header.
+    pub gas_limit: u64,
+    /// The beneficiary, from the Zenith header's reward address.
+    pub beneficiary: Address,
+    /// The root of the ordered transactions trie. See
+    /// [`ZenithBlock::transactions_root`].
+    pub transactions_root: B256,
+}
+
+impl RollupHeader {
+    /// Derive the rollup header of a block, given the parent rollup header
+    /// and the timestamp of the host block in which the block was submitted.
+    ///
+    /// Fails if the timestamp is not later than the parent's, or if the
+    /// parent number is `u64::MAX`.
+    pub fn derive<C: Coder>(
+        block: &ZenithBlock<C>,
+        parent: &Header,
+        host_timestamp: u64,
+    ) -> Result<Self, HeaderLinkError> {
+        let header = Self {
+            number: next_number(parent)?,
+            parent_hash: parent.hash_slow(),
+            timestamp: host_timestamp,
+            gas_limit: block.gas_limit(),
+            beneficiary: block.reward_address(),
+            transactions_root: block.transactions_root(),
+        };
+        header.check_parent(parent)?;
+        Ok(header)
+    }
+
+    /// Check that the header links to the parent: its number is one greater,
+    /// its parent hash is the parent's hash, and its timestamp is later.
+    pub fn check_parent(&self, parent: &Header) -> Result<(), HeaderLinkError> {
+        check_link(self.number, self.parent_hash, self.timestamp, parent)
+    }
+
+    /// Convert to a [`Header`], leaving the fields not derived from the block
+    /// at their defaults.
+    pub fn to_header(&self) -> Header {
+        Header {
+            parent_hash: self.parent_hash,
+            beneficiary: self.beneficiary,
+            transactions_root: self.transactions_root,
+            number: self.number,
+            gas_limit: self.gas_limit,
+            timestamp: self.timestamp,
+            ..Default::default()
+        }
+    }
+
+    /// Compute the hash of the header. See [`RollupHeader::to_header`].
+    pub fn hash_slow(&self) -> B256 {
+        self.to_header().hash_slow()
+    }
+}
+
+impl From<RollupHeader> for Header {
+    fn from(header: RollupHeader) -> Self {
+        header.to_header()
+    }
+}
+
+/// Check that a [`Header`] links to its parent. See
+/// [`RollupHeader::check_parent`].
+pub fn check_header_link(header: &Header, parent: &Header) -> Result<(), HeaderLinkError> {
+    check_link(header.number, header.parent_hash, header.timestamp, parent)
+}
+
+fn next_number(parent: &Header) -> Result<u64, HeaderLinkError> {
+    parent.number.checked_add(1).ok_or(HeaderLinkError::NumberOverflow { parent: parent.number })
+}
+
+fn check_link(
+    number: u64,
+    parent_hash: B256,
+    timestamp: u64,
+    parent: &Header,
+) -> Result<(), HeaderLinkError> {
+    let expected = next_number(parent)?;
+    if number != expected {
+        return Err(HeaderLinkError::Number { expected, got: number });
+    }
+    let expected = parent.hash_slow();
+    if parent_hash != expected {
+        return Err(HeaderLinkError::ParentHash { expected, got: parent_hash });
+    }
+    if timestamp <= parent.timestamp {
+        return Err(HeaderLinkError::Timestamp { parent: parent.timestamp, got: timestamp });
+    }
+    Ok(())
+}
+
+impl<C> ZenithBlock<C>
+where
+    C: Coder,
+{
+    /// Derive the rollup header of the block. See [`RollupHeader::derive`].
+    pub fn rollup_header(
+        &self,
+        parent: &Header,
+        host_timestamp: u64,
+    ) -> Result<RollupHeader, HeaderLinkError> {
+        RollupHeader::derive(self, parent, host_timestamp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{header, tx};
+
+    fn genesis() -> Header {
+        Header { number: 5, timestamp: 1_000, gas_limit: 30_000_000, ..Default::default() }
+    }
+
+    #[test]
+    fn chain() {
+        let genesis = genesis();
+        let first: ZenithBlock = ZenithBlock::new(header(), vec![tx(0, 10)]);
+        let h1 = first.rollup_header(&genesis, 1_012).unwrap();
+
+        assert_eq!(h1.number, 6);
+        assert_eq!(h1.parent_hash, genesis.hash_slow());
+        assert_eq!(h1.gas_limit, 30_000_000);
+        assert_eq!(h1.beneficiary, first.reward_address());
+        assert_eq!(h1.transactions_root, first.transactions_root());
+
+        let h1: Header = h1.into();
+        assert_eq!(check_header_link(&h1, &genesis), Ok(()));
+
+        let second: ZenithBlock = ZenithBlock::new(header(), vec![]);
+        let h2 = second.rollup_header(&h1, 1_024).unwrap();
+        assert_eq!(h2.number, 7);
+        assert_eq!(h2.parent_hash, h1.hash_slow());
+        assert_eq!(h2.check_parent(&h1), Ok(()));
+    }
+
+    #[test]
+    fn bad_links() {
+        let genesis = genesis();
+        let block: ZenithBlock = ZenithBlock::new(header(), vec![]);
+
+        assert_eq!(
+            block.rollup_header(&genesis, 1_000),
+            Err(HeaderLinkError::Timestamp { parent: 1_000, got: 1_000 })
+        );
+
+        let h1 = block.rollup_header(&genesis, 1_012).unwrap();
+        let other = Header { number: 5, timestamp: 999, ..Default::default() };
+        assert!(matches!(h1.check_parent(&other), Err(HeaderLinkError::ParentHash { .. })));
+
+        let mut skipped = h1.to_header();
+        skipped.number = 7;
+        assert_eq!(
+            check_header_link(&skipped, &genesis),
+            Err(HeaderLinkError::Number { expected: 6, got: 7 })
+        );
+
+        let last = Header { number: u64::MAX, ..Default::default() };
+        assert_eq!(
+            block.rollup_header(&last, 1_012),
+            Err(HeaderLinkError::NumberOverflow { parent: u64::MAX })
+        );
+        assert_eq!(
+            check_header_link(&skipped, &last),
+            Err(HeaderLinkError::NumberOverflow { parent: u64::MAX })
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{Coder, ZenithBlock, ZenithTransaction};
+use alloy::primitives::{Address, SignatureError};
+
+/// A transaction whose signer can be recovered from its signature.
+pub trait RecoverSigner {
+    /// Recover the signer of the transaction.
+    fn recover_signer(&self) -> Result<Address, SignatureError>;
+}
+
+impl RecoverSigner for ZenithTransaction {
+    fn recover_signer(&self) -> Result<Address, SignatureError> {
+        ZenithTransaction::recover_signer(self)
+    }
+}
+
+/// Error returned when the sender of a transaction in a [`ZenithBlock`] cannot
+/// be recovered.
+#[derive(Debug, thiserror::Error)]
+#[error("failed to recover sender of transaction {index}: {source}")]
+pub struct SenderRecoveryError {
+    /// The index of the first transaction whose sender could not be
+    /// recovered.
+    pub index: usize,
+    /// The underlying signature error.
+    pub source: SignatureError,
+}
+
+#[cfg(not(feature = "parallel"))]
+fn recover_all<T: RecoverSigner>(txs: &[T]) -> Vec<Result<Address, SignatureError>> {
+    txs.iter().map(RecoverSigner::recover_signer).collect()
+}
+
+#[cfg(feature = "parallel")]
+fn recover_all<T: RecoverSigner + Sync>(txs: &[T]) -> Vec<Result<Address, SignatureError>> {
+    use rayon::prelude::*;
+    txs.par_iter().map(RecoverSigner::recover_signer).collect()
+}
+
+impl<C> ZenithBlock<C>
+where
+    C: Coder,
+    C::Tx: RecoverSigner + Sync,
+{
+    /// Recover the senders of the transactions in the block, in order.
+    ///
+    /// Senders are memoized until the transactions are next modified. With
+    /// the `parallel` feature enabled, recovery runs in parallel. Failures are
+    /// not memoized.
+    pub fn senders(&self) -> Result<&[Address], SenderRecoveryError> {
+        if let Some(senders) = self.senders.get() {
+            return Ok(senders);
+        }
+        let senders = recover_all(&self.transactions)
+            .into_iter()
+            .enumerate()
+            .map(|(index, res)| res.map_err(|source| SenderRecoveryError { index, source }))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(self.senders.get_or_init(|| senders))
+    }
+
+    /// Iterate over the transactions in the block, paired with their senders.
+    /// See [`ZenithBlock::senders`].
+    pub fn transactions_with_senders(
+        &self,
+    ) -> Result<impl Iterator<Item = (Address, &C::Tx)> + '_, SenderRecoveryError> {
+        let senders = self.senders()?;
+        Ok(senders.iter().copied().zip(self.transactions.iter()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{header, signed_tx, signer};
+    use alloy::consensus::{SignableTransaction, TxEip1559};
+    use alloy::primitives::{PrimitiveSignature, U256};
+
+    #[test]
+    fn recovers_and_memoizes() {
+        let (a, b) = (signer(1), signer(2));
+
+        let mut block: ZenithBlock =
+            ZenithBlock::new(header(), vec![signed_tx(&a, 0, 21_000), signed_tx(&b, 0, 21_000)]);
+        assert_eq!(block.senders().unwrap(), &[a.address(), b.address()]);
+        assert!(block.senders.get().is_some());
+
+        let pairs: Vec<_> =
+            block.transactions_with_senders().unwrap().map(|(s, tx)| (s, tx.clone())).collect();
+        assert_eq!(pairs[1], (b.address(), signed_tx(&b, 0, 21_000)));
+
+        // modifying the transactions clears the memoized senders
+        block.push_transaction(signed_tx(&a, 1, 21_000));
+        assert!(block.senders.get().is_none());
+        assert_eq!(block.senders().unwrap(), &[a.address(), b.address(), a.address()]);
+    }
+
+    #[test]
+    fn reports_first_failure() {
+        let a = signer(1);
+        // s = 0 is never a valid signature
+        let bad: ZenithTransaction = TxEip1559::default()
+            .into_signed(PrimitiveSignature::new(U256::from(1), U256::ZERO, false))
+            .into();
+
+        let block: ZenithBlock =
+            ZenithBlock::new(header(), vec![signed_tx(&a, 0, 21_000), bad.clone(), bad]);
+        let err = block.senders().unwrap_err();
+        assert_eq!(err.index, 1);
+        assert!(block.senders.get().is_none());
+    }
+}
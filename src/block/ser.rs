@@ -0,0 +1,144 @@
+use crate::Zenith::BlockHeader as ZenithHeader;
+use crate::{Coder, ZenithBlock};
+use alloy::primitives::{Bytes, B256};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Compact form of a [`ZenithBlock`]: the header and the encoded block data.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Compact {
+    header: ZenithHeader,
+    encoded_txns: Bytes,
+}
+
+/// Expanded form of a [`ZenithBlock`]: the header, the block data hash, and
+/// the transactions.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Expanded<T> {
+    header: ZenithHeader,
+    block_data_hash: B256,
+    transactions: T,
+}
+
+/// Serializes in compact form, as the header and the hex-encoded block data.
+/// See [`ExpandedBlock`] for a form with JSON transaction objects.
+///
+/// Deserializing decodes the block data via
+/// [`ZenithBlock::from_header_and_data`], so the block data hash is that of
+/// the serialized data.
+impl<C: Coder> Serialize for ZenithBlock<C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Compact { header: self.header, encoded_txns: Bytes::copy_from_slice(self.encoded_txns()) }
+            .serialize(serializer)
+    }
+}
+
+impl<'de, C: Coder> Deserialize<'de> for ZenithBlock<C> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Compact { header, encoded_txns } = Compact::deserialize(deserializer)?;
+        Ok(Self::from_header_and_data(header, encoded_txns))
+    }
+}
+
+/// A [`ZenithBlock`] that serializes in expanded form, as the header, the
+/// block data hash, and the transactions as objects.
+///
+/// Deserializing re-encodes the transactions, and fails if the hash of the
+/// result does not match the serialized block data hash. Blocks whose data
+/// contained items that failed to decode cannot round-trip in this form, and
+/// should be serialized in the compact form of [`ZenithBlock`] instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpandedBlock<C: Coder = crate::Alloy2718Coder>(pub ZenithBlock<C>);
+
+impl<C: Coder> From<ZenithBlock<C>> for ExpandedBlock<C> {
+    fn from(block: ZenithBlock<C>) -> Self {
+        Self(block)
+    }
+}
+
+impl<C: Coder> From<ExpandedBlock<C>> for ZenithBlock<C> {
+    fn from(block: ExpandedBlock<C>) -> Self {
+        block.0
+    }
+}
+
+impl<C> Serialize for ExpandedBlock<C>
+where
+    C: Coder,
+    C::Tx: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Expanded {
+            header: self.0.header,
+            block_data_hash: self.0.block_data_hash(),
+            transactions: self.0.transactions(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, C> Deserialize<'de> for ExpandedBlock<C>
+where
+    C: Coder,
+    C::Tx: DeserializeOwned,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Expanded { header, block_data_hash, transactions } =
+            Expanded::<Vec<C::Tx>>::deserialize(deserializer)?;
+        let block = ZenithBlock::new(header, transactions);
+        let actual = block.block_data_hash();
+        if actual != block_data_hash {
+            return Err(serde::de::Error::custom(format!(
+                "block data hash mismatch: expected {block_data_hash}, got {actual}"
+            )));
+        }
+        Ok(Self(block))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{header, tx};
+    use crate::{encode_txns, Alloy2718Coder};
+
+    #[test]
+    fn compact() {
+        let block: ZenithBlock = ZenithBlock::new(header(), vec![tx(0, 10), tx(1, 20)]);
+        let json = serde_json::to_value(&block).unwrap();
+        assert_eq!(
+            json["encodedTxns"],
+            format!("{}", Bytes::copy_from_slice(block.encoded_txns()))
+        );
+
+        let de: ZenithBlock = serde_json::from_value(json).unwrap();
+        assert_eq!(de.transactions(), block.transactions());
+        assert_eq!(de.block_data_hash(), block.block_data_hash());
+
+        // data with undecodable items keeps its hash
+        let mut data = encode_txns::<Alloy2718Coder>(&[tx(0, 10)]);
+        data.push(0);
+        let lossy: ZenithBlock = ZenithBlock::from_header_and_data(header(), &data);
+        let de: ZenithBlock =
+            serde_json::from_str(&serde_json::to_string(&lossy).unwrap()).unwrap();
+        assert_eq!(de.encoded_txns(), data.as_slice());
+        assert_eq!(de.block_data_hash(), lossy.block_data_hash());
+    }
+
+    #[test]
+    fn expanded() {
+        let block: ZenithBlock = ZenithBlock::new(header(), vec![tx(0, 10), tx(1, 20)]);
+        let json = serde_json::to_value(ExpandedBlock::from(block.clone())).unwrap();
+        assert_eq!(json["transactions"].as_array().unwrap().len(), 2);
+        assert_eq!(json["transactions"][1]["nonce"], "0x1");
+
+        let de: ExpandedBlock = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(de.0.transactions(), block.transactions());
+        assert_eq!(de.0.block_data_hash(), block.block_data_hash());
+
+        let mut tampered = json;
+        tampered["blockDataHash"] = serde_json::to_value(B256::ZERO).unwrap();
+        assert!(serde_json::from_value::<ExpandedBlock>(tampered).is_err());
+    }
+}
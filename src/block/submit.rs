@@ -0,0 +1,387 @@
+use crate::BundleHelper::submitCall;
+use crate::Zenith::{submitBlockCall, BlockHeader as ZenithHeader};
+use crate::{
+    AggregateOrders, Alloy2718Coder, BlobError, BlockValidationError, Coder, SignRequest,
+    SignResponse, ZenithBlock,
+};
+use alloy::consensus::{BlobTransactionSidecar, Transaction, TxEip4844Variant, TxEnvelope};
+use alloy::primitives::{Address, PrimitiveSignature, SignatureError, B256, U256};
+use alloy::sol_types::SolCall;
+
+/// Errors that may occur when reconstructing a [`ZenithBlock`] from a host
+/// transaction.
+#[derive(Debug, thiserror::Error)]
+pub enum SubmitBlockError {
+    /// The transaction was not sent to the Zenith contract.
+    #[error("transaction sent to {got:?}, not the Zenith contract {expected}")]
+    WrongContract {
+        /// The address of the Zenith contract.
+        expected: Address,
+        /// The recipient of the transaction, or `None` for a contract
+        /// creation.
+        got: Option<Address>,
+    },
+    /// The transaction input is not the expected call.
+    #[error("invalid calldata: {0}")]
+    InvalidCalldata(#[from] alloy::sol_types::Error),
+    /// The host transaction has no chain id, so the signing hash cannot be
+    /// computed.
+    #[error("host transaction has no chain id")]
+    MissingChainId,
+    /// The `v` value of the sequencer signature is not 27 or 28.
+    #[error("invalid signature v value: {0}")]
+    InvalidV(u8),
+    /// The sequencer signature could not be recovered.
+    #[error(transparent)]
+    Signature(#[from] SignatureError),
+    /// The block data is carried in blobs, but no sidecar was available.
+    #[error("blob transaction has no sidecar")]
+    MissingSidecar,
+    /// The sidecar does not match the blob versioned hashes of the
+    /// transaction.
+    #[error("sidecar does not match transaction blob hashes")]
+    SidecarMismatch,
+    /// The block data could not be unpacked from the blobs.
+    #[error(transparent)]
+    Blob(#[from] BlobError),
+    /// The block data does not match the header.
+    #[error(transparent)]
+    Validation(#[from] BlockValidationError),
+}
+
+/// A [`ZenithBlock`] reconstructed from a host transaction that called
+/// [`crate::Zenith::submitBlockCall`], along with the sequencer signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmittedBlock<C: Coder = Alloy2718Coder> {
+    /// The block.
+    pub block: ZenithBlock<C>,
+    /// The sequencer that signed the block.
+    pub sequencer: Address,
+    /// The sequencer signature, and the request it signed.
+    pub sig: SignResponse,
+}
+
+impl<C> SubmittedBlock<C>
+where
+    C: Coder,
+{
+    /// Reconstruct a block from a host transaction that called
+    /// `submitBlock` on the Zenith contract at `zenith`.
+    ///
+    /// The block data is taken from the calldata, or, if the transaction
+    /// carries blobs, from its sidecar. Blob transactions without a sidecar
+    /// must use [`SubmittedBlock::from_host_tx_and_sidecar`].
+    pub fn from_host_tx(tx: &TxEnvelope, zenith: Address) -> Result<Self, SubmitBlockError> {
+        Self::from_host_tx_and_sidecar(tx, zenith, None)
+    }
+
+    /// Reconstruct a block from a host transaction that called
+    /// `submitBlock`, using the provided sidecar for blob transactions whose
+    /// envelope does not include one.
+    ///
+    /// This checks that the transaction was sent to `zenith`, that the
+    /// sidecar matches the versioned hashes of the transaction, that the
+    /// block data matches the `blockDataHash` in the header, and recovers
+    /// the sequencer from the signature.
+    pub fn from_host_tx_and_sidecar(
+        tx: &TxEnvelope,
+        zenith: Address,
+        sidecar: Option<&BlobTransactionSidecar>,
+    ) -> Result<Self, SubmitBlockError> {
+        let to = tx.to();
+        if to != Some(zenith) {
+            return Err(SubmitBlockError::WrongContract { expected: zenith, got: to });
+        }
+        let call = submitBlockCall::abi_decode(tx.input(), true)?;
+        let host_chain_id = tx.chain_id().ok_or(SubmitBlockError::MissingChainId)?;
+        let sig = signature_from_vrs(call.v, call.r, call.s)?;
+        let sig = SignResponse { req: sign_request(&call.header, host_chain_id), sig };
+        let sequencer = sig.signer()?;
+
+        let block = match tx.blob_versioned_hashes() {
+            Some(hashes) if !hashes.is_empty() => {
+                let sidecar = match tx {
+                    TxEnvelope::Eip4844(signed) => match signed.tx() {
+                        TxEip4844Variant::TxEip4844WithSidecar(tx) => Some(tx.sidecar()),
+                        TxEip4844Variant::TxEip4844(_) => sidecar,
+                    },
+                    _ => sidecar,
+                }
+                .ok_or(SubmitBlockError::MissingSidecar)?;
+
+                if !sidecar.versioned_hashes().eq(hashes.iter().copied()) {
+                    return Err(SubmitBlockError::SidecarMismatch);
+                }
+                ZenithBlock::from_header_and_sidecar(call.header, sidecar)?
+            }
+            _ => ZenithBlock::from_header_and_data(call.header, &call._4),
+        };
+        block.validate()?;
+
+        Ok(Self { block, sequencer, sig })
+    }
+}
+
+/// A block header and host fills decoded from a host transaction that called
+/// [`crate::BundleHelper::submitCall`], along with the sequencer signature.
+///
+/// The `BundleHelper` submits the block with empty calldata, so the block
+/// data must be retrieved from the blobs of the transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleSubmission {
+    /// The block header.
+    pub header: ZenithHeader,
+    /// The sequencer that signed the block.
+    pub sequencer: Address,
+    /// The sequencer signature, and the request it signed.
+    pub sig: SignResponse,
+    /// The host fills, aggregated per asset and recipient.
+    pub fills: AggregateOrders,
+}
+
+impl BundleSubmission {
+    /// Decode a `BundleHelper.submit` call made on the host chain with the
+    /// given chain id.
+    pub fn from_call(call: &submitCall, host_chain_id: u64) -> Result<Self, SubmitBlockError> {
+        let header: ZenithHeader = call.header.into();
+        let sig = signature_from_vrs(call.v, call.r, call.s)?;
+        let sig = SignResponse { req: sign_request(&header, host_chain_id), sig };
+        let sequencer = sig.signer()?;
+
+        let outputs: Vec<_> = call.fills.iter().flat_map(|fill| fill.outputs()).collect();
+        let mut fills = AggregateOrders::new();
+        fills.ingest_fill(&outputs);
+
+        Ok(Self { header, sequencer, sig, fills })
+    }
+
+    /// Decode a host transaction that called `BundleHelper.submit`.
+    pub fn from_host_tx(tx: &TxEnvelope) -> Result<Self, SubmitBlockError> {
+        let call = submitCall::abi_decode(tx.input(), true)?;
+        let host_chain_id = tx.chain_id().ok_or(SubmitBlockError::MissingChainId)?;
+        Self::from_call(&call, host_chain_id)
+    }
+
+    /// Unpack the block data from the blob sidecar of the transaction, and
+    /// check it against the header.
+    pub fn block_from_sidecar<C: Coder>(
+        &self,
+        sidecar: &BlobTransactionSidecar,
+    ) -> Result<ZenithBlock<C>, SubmitBlockError> {
+        let block = ZenithBlock::from_header_and_sidecar(self.header, sidecar)?;
+        block.validate()?;
+        Ok(block)
+    }
+}
+
+/// Construct the [`SignRequest`] that the sequencer signed for the header.
+fn sign_request(header: &ZenithHeader, host_chain_id: u64) -> SignRequest {
+    SignRequest {
+        host_block_number: header.hostBlockNumber,
+        host_chain_id: U256::from(host_chain_id),
+        ru_chain_id: header.rollupChainId,
+        gas_limit: header.gasLimit,
+        ru_reward_address: header.rewardAddress,
+        contents: header.blockDataHash,
+    }
+}
+
+/// Convert the `v`, `r`, `s` arguments of a `submitBlock` call into a
+/// signature. The contract uses `ecrecover`, so `v` must be 27 or 28.
+fn signature_from_vrs(v: u8, r: B256, s: B256) -> Result<PrimitiveSignature, SubmitBlockError> {
+    let parity = match v {
+        27 => false,
+        28 => true,
+        _ => return Err(SubmitBlockError::InvalidV(v)),
+    };
+    Ok(PrimitiveSignature::from_scalars_and_parity(r, s, parity))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{header, signer, tx};
+    use alloy::consensus::{SignableTransaction, TxEip1559, TxEip4844, TxEip4844WithSidecar};
+    use alloy::primitives::{Bytes, TxKind};
+    use alloy::signers::SignerSync;
+
+    const HOST_CHAIN_ID: u64 = 1;
+    const ZENITH: Address = Address::repeat_byte(0x22);
+
+    fn signed_call(block: &ZenithBlock, data: Bytes) -> (submitBlockCall, Address) {
+        let mut header = *block.header();
+        header.blockDataHash = block.block_data_hash();
+
+        let signer = signer(8);
+        let sig =
+            signer.sign_hash_sync(&sign_request(&header, HOST_CHAIN_ID).signing_hash()).unwrap();
+
+        let call = submitBlockCall {
+            header,
+            v: 27 + sig.v() as u8,
+            r: sig.r().into(),
+            s: sig.s().into(),
+            _4: data,
+        };
+        (call, signer.address())
+    }
+
+    fn host_sig() -> PrimitiveSignature {
+        PrimitiveSignature::from_scalars_and_parity(
+            B256::repeat_byte(1),
+            B256::repeat_byte(2),
+            false,
+        )
+    }
+
+    #[test]
+    fn calldata() {
+        let block: ZenithBlock = ZenithBlock::new(header(), vec![tx(0, 10), tx(1, 20)]);
+        let (call, signer) = signed_call(&block, block.encoded_txns().to_vec().into());
+
+        let host_tx: TxEnvelope = TxEip1559 {
+            chain_id: HOST_CHAIN_ID,
+            to: TxKind::Call(ZENITH),
+            input: call.abi_encode().into(),
+            ..Default::default()
+        }
+        .into_signed(host_sig())
+        .into();
+
+        let submitted = SubmittedBlock::<Alloy2718Coder>::from_host_tx(&host_tx, ZENITH).unwrap();
+        assert_eq!(submitted.sequencer, signer);
+        assert_eq!(submitted.sig.signer().unwrap(), signer);
+        assert_eq!(submitted.block.transactions(), block.transactions());
+        assert_eq!(submitted.block.block_data_hash(), block.block_data_hash());
+
+        // the same call sent to another contract
+        let other = Address::repeat_byte(0x23);
+        assert!(matches!(
+            SubmittedBlock::<Alloy2718Coder>::from_host_tx(&host_tx, other),
+            Err(SubmitBlockError::WrongContract { expected, got: Some(ZENITH) }) if expected == other
+        ));
+    }
+
+    #[test]
+    fn blobs() {
+        let block: ZenithBlock = ZenithBlock::new(header(), vec![tx(0, 10), tx(1, 20)]);
+        let (call, signer) = signed_call(&block, Bytes::new());
+        let sidecar = block.blob_sidecar().unwrap();
+
+        let tx = TxEip4844 {
+            chain_id: HOST_CHAIN_ID,
+            to: ZENITH,
+            input: call.abi_encode().into(),
+            blob_versioned_hashes: sidecar.versioned_hashes().collect(),
+            ..Default::default()
+        };
+
+        // without a sidecar in the envelope, one must be provided
+        let bare: TxEnvelope = tx.clone().into_signed(host_sig()).into();
+        assert!(matches!(
+            SubmittedBlock::<Alloy2718Coder>::from_host_tx(&bare, ZENITH),
+            Err(SubmitBlockError::MissingSidecar)
+        ));
+        let submitted = SubmittedBlock::<Alloy2718Coder>::from_host_tx_and_sidecar(
+            &bare,
+            ZENITH,
+            Some(&sidecar),
+        )
+        .unwrap();
+        assert_eq!(submitted.sequencer, signer);
+        assert_eq!(submitted.block.transactions(), block.transactions());
+
+        // with a sidecar in the envelope
+        let with_sidecar: TxEnvelope =
+            TxEip4844Variant::from(TxEip4844WithSidecar::from_tx_and_sidecar(tx, sidecar))
+                .into_signed(host_sig())
+                .into();
+        let submitted =
+            SubmittedBlock::<Alloy2718Coder>::from_host_tx(&with_sidecar, ZENITH).unwrap();
+        assert_eq!(submitted.block.block_data_hash(), block.block_data_hash());
+    }
+
+    #[test]
+    fn bundle_helper() {
+        use crate::BundleHelper;
+        use alloy::primitives::U256;
+
+        let block: ZenithBlock = ZenithBlock::new(header(), vec![tx(0, 10)]);
+        let (call, signer) = signed_call(&block, Bytes::new());
+
+        let token = Address::repeat_byte(0x33);
+        let user = Address::repeat_byte(0x44);
+        let output = |amount: u64| BundleHelper::Output {
+            token,
+            amount: U256::from(amount),
+            recipient: user,
+            chainId: HOST_CHAIN_ID as u32,
+        };
+        let fill = |outputs| BundleHelper::FillPermit2 {
+            outputs,
+            permit2: BundleHelper::Permit2Batch {
+                permit: BundleHelper::PermitBatchTransferFrom {
+                    permitted: vec![],
+                    nonce: U256::ZERO,
+                    deadline: U256::ZERO,
+                },
+                owner: Address::ZERO,
+                signature: Bytes::new(),
+            },
+        };
+
+        let submit = BundleHelper::submitCall {
+            fills: vec![fill(vec![output(5), output(6)]), fill(vec![output(7)])],
+            header: BundleHelper::BlockHeader {
+                rollupChainId: call.header.rollupChainId,
+                hostBlockNumber: call.header.hostBlockNumber,
+                gasLimit: call.header.gasLimit,
+                rewardAddress: call.header.rewardAddress,
+                blockDataHash: call.header.blockDataHash,
+            },
+            v: call.v,
+            r: call.r,
+            s: call.s,
+        };
+
+        let host_tx: TxEnvelope = TxEip1559 {
+            chain_id: HOST_CHAIN_ID,
+            input: submit.abi_encode().into(),
+            ..Default::default()
+        }
+        .into_signed(host_sig())
+        .into();
+
+        let submission = BundleSubmission::from_host_tx(&host_tx).unwrap();
+        assert_eq!(submission.header, call.header);
+        assert_eq!(submission.sequencer, signer);
+        assert_eq!(
+            submission.fills.outputs.get(&(HOST_CHAIN_ID, token)).and_then(|m| m.get(&user)),
+            Some(&U256::from(18))
+        );
+
+        let sidecar = block.blob_sidecar().unwrap();
+        let decoded: ZenithBlock = submission.block_from_sidecar(&sidecar).unwrap();
+        assert_eq!(decoded.transactions(), block.transactions());
+    }
+
+    #[test]
+    fn bad_data() {
+        let block: ZenithBlock = ZenithBlock::new(header(), vec![tx(0, 10)]);
+        let (call, _) = signed_call(&block, Bytes::from_static(&[0xc0]));
+
+        let host_tx: TxEnvelope = TxEip1559 {
+            chain_id: HOST_CHAIN_ID,
+            to: TxKind::Call(ZENITH),
+            input: call.abi_encode().into(),
+            ..Default::default()
+        }
+        .into_signed(host_sig())
+        .into();
+
+        assert!(matches!(
+            SubmittedBlock::<Alloy2718Coder>::from_host_tx(&host_tx, ZENITH),
+            Err(SubmitBlockError::Validation(BlockValidationError::DataHash { .. }))
+        ));
+    }
+}
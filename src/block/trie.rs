@@ -0,0 +1,133 @@
+use crate::{Coder, ZenithBlock};
+use alloy::consensus::proofs::ordered_trie_root_with_encoder;
+use alloy::primitives::{Bytes, B256};
+use alloy_trie::proof::{verify_proof, ProofRetainer, ProofVerificationError};
+use alloy_trie::root::adjust_index_for_rlp;
+use alloy_trie::{HashBuilder, Nibbles};
+use serde::{Deserialize, Serialize};
+
+/// Error returned when a [`TxInclusionProof`] fails to verify.
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+#[error("invalid transaction inclusion proof: {0}")]
+pub struct TxProofError(pub Box<ProofVerificationError>);
+
+impl From<ProofVerificationError> for TxProofError {
+    fn from(err: ProofVerificationError) -> Self {
+        Self(Box::new(err))
+    }
+}
+
+/// The trie key of the transaction at `index`.
+fn tx_key(index: usize) -> Nibbles {
+    Nibbles::unpack(alloy::rlp::encode_fixed_size(&index))
+}
+
+/// A Merkle-Patricia inclusion proof for a transaction in the transactions
+/// trie of a [`ZenithBlock`]. See [`ZenithBlock::transactions_root`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxInclusionProof {
+    /// The index of the transaction in the block.
+    pub index: usize,
+    /// The RLP-encoded trie nodes on the path from the root to the
+    /// transaction, in order.
+    pub proof: Vec<Bytes>,
+}
+
+impl TxInclusionProof {
+    /// Verify that the transaction with the given encoding is at
+    /// [`Self::index`] in the trie with the given root.
+    pub fn verify_encoded(&self, root: B256, encoded_tx: &[u8]) -> Result<(), TxProofError> {
+        verify_proof(root, tx_key(self.index), Some(encoded_tx.to_vec()), &self.proof)
+            .map_err(Into::into)
+    }
+
+    /// Verify that the transaction is at [`Self::index`] in the trie with the
+    /// given root, encoding it with the coder `C`.
+    pub fn verify<C: Coder>(&self, root: B256, tx: &C::Tx) -> Result<(), TxProofError> {
+        self.verify_encoded(root, &C::encode(tx))
+    }
+}
+
+impl<C> ZenithBlock<C>
+where
+    C: Coder,
+{
+    /// Compute the root of the ordered transactions trie, as used for the
+    /// `transactionsRoot` of an Ethereum block. Keys are the RLP-encoded
+    /// indices of the transactions, and values are their encodings by the
+    /// coder `C`.
+    pub fn transactions_root(&self) -> B256 {
+        ordered_trie_root_with_encoder(&self.transactions, |tx, buf| C::encode_into(tx, buf))
+    }
+
+    /// Produce an inclusion proof for the transaction at `index` against
+    /// [`ZenithBlock::transactions_root`]. Returns `None` if the index is out
+    /// of bounds.
+    pub fn transaction_proof(&self, index: usize) -> Option<TxInclusionProof> {
+        let len = self.transactions.len();
+        if index >= len {
+            return None;
+        }
+        let target = tx_key(index);
+        let mut hb =
+            HashBuilder::default().with_proof_retainer(ProofRetainer::new(vec![target.clone()]));
+
+        // Leaves must be added in key order, which differs from index order.
+        let mut value = Vec::new();
+        for i in 0..len {
+            let index = adjust_index_for_rlp(i, len);
+            value.clear();
+            C::encode_into(&self.transactions[index], &mut value);
+            hb.add_leaf(tx_key(index), &value);
+        }
+        hb.root();
+
+        let proof = hb
+            .take_proof_nodes()
+            .matching_nodes_sorted(&target)
+            .into_iter()
+            .map(|(_, node)| node)
+            .collect();
+        Some(TxInclusionProof { index, proof })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{header, tx};
+    use crate::Alloy2718Coder;
+    use alloy::consensus::proofs::calculate_transaction_root;
+
+    #[test]
+    fn root_matches_ethereum() {
+        for n in [0, 1, 2, 130] {
+            let txs: Vec<_> = (0..n).map(|i| tx(i, 10)).collect();
+            let block: ZenithBlock = ZenithBlock::new(header(), txs.clone());
+            assert_eq!(block.transactions_root(), calculate_transaction_root(&txs));
+        }
+    }
+
+    #[test]
+    fn proofs() {
+        for n in [1, 2, 130] {
+            let block: ZenithBlock =
+                ZenithBlock::new(header(), (0..n).map(|i| tx(i, 10)).collect());
+            let root = block.transactions_root();
+
+            for index in [0, n as usize / 2, n as usize - 1, 0x7f.min(n as usize - 1)] {
+                let proof = block.transaction_proof(index).unwrap();
+                let tx = &block.transactions()[index];
+                proof.verify::<Alloy2718Coder>(root, tx).unwrap();
+
+                // the wrong transaction does not verify
+                let other = &block.transactions()[(index + 1) % n as usize];
+                if n > 1 {
+                    assert!(proof.verify::<Alloy2718Coder>(root, other).is_err());
+                }
+            }
+            assert!(block.transaction_proof(n as usize).is_none());
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::Zenith::{BlockHeader as ZenithHeader, BlockSubmitted};
+use crate::{Coder, ZenithBlock};
+use alloy::primitives::{Address, B256, U256};
+
+/// Errors produced when a [`ZenithBlock`] does not match the commitment made
+/// on the host chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum BlockValidationError {
+    /// The hash of the block data does not match the committed hash.
+    #[error("block data hash mismatch: expected {expected}, got {actual}")]
+    DataHash {
+        /// The committed block data hash.
+        expected: B256,
+        /// The hash of the block data.
+        actual: B256,
+    },
+    /// The rollup chain id does not match.
+    #[error("rollup chain id mismatch: expected {expected}, got {actual}")]
+    ChainId {
+        /// The committed rollup chain id.
+        expected: U256,
+        /// The rollup chain id of the block.
+        actual: U256,
+    },
+    /// The host block number does not match.
+    #[error("host block number mismatch: expected {expected}, got {actual}")]
+    HostBlockNumber {
+        /// The host block number of the commitment.
+        expected: U256,
+        /// The host block number of the block.
+        actual: U256,
+    },
+    /// The gas limit does not match.
+    #[error("gas limit mismatch: expected {expected}, got {actual}")]
+    GasLimit {
+        /// The committed gas limit.
+        expected: U256,
+        /// The gas limit of the block.
+        actual: U256,
+    },
+    /// The reward address does not match.
+    #[error("reward address mismatch: expected {expected}, got {actual}")]
+    RewardAddress {
+        /// The committed reward address.
+        expected: Address,
+        /// The reward address of the block.
+        actual: Address,
+    },
+}
+
+impl<C> ZenithBlock<C>
+where
+    C: Coder,
+{
+    /// Check that the hash of the block data matches the `blockDataHash` in
+    /// the block's own header.
+    ///
+    /// Blocks created via [`ZenithBlock::from_header_and_data`] take their
+    /// header from the host chain, so this checks that the data matches what
+    /// the sequencer committed to.
+    pub fn validate(&self) -> Result<(), BlockValidationError> {
+        let expected = self.header.blockDataHash;
+        let actual = self.block_data_hash();
+        if expected != actual {
+            return Err(BlockValidationError::DataHash { expected, actual });
+        }
+        Ok(())
+    }
+
+    /// Check the block against a header committed on the host chain. This
+    /// checks the rollup chain id, host block number, gas limit, reward
+    /// address, and that the hash of the block data matches the committed
+    /// `blockDataHash`.
+    pub fn validate_against_header(
+        &self,
+        committed: &ZenithHeader,
+    ) -> Result<(), BlockValidationError> {
+        let header = &self.header;
+        if header.rollupChainId != committed.rollupChainId {
+            return Err(BlockValidationError::ChainId {
+                expected: committed.rollupChainId,
+                actual: header.rollupChainId,
+            });
+        }
+        if header.hostBlockNumber != committed.hostBlockNumber {
+            return Err(BlockValidationError::HostBlockNumber {
+                expected: committed.hostBlockNumber,
+                actual: header.hostBlockNumber,
+            });
+        }
+        if header.gasLimit != committed.gasLimit {
+            return Err(BlockValidationError::GasLimit {
+                expected: committed.gasLimit,
+                actual: header.gasLimit,
+            });
+        }
+        if header.rewardAddress != committed.rewardAddress {
+            return Err(BlockValidationError::RewardAddress {
+                expected: committed.rewardAddress,
+                actual: header.rewardAddress,
+            });
+        }
+
+        let actual = self.block_data_hash();
+        if actual != committed.blockDataHash {
+            return Err(BlockValidationError::DataHash {
+                expected: committed.blockDataHash,
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check the block against a [`BlockSubmitted`] event emitted in the host
+    /// block with the given number. See
+    /// [`ZenithBlock::validate_against_header`].
+    pub fn validate_against_submitted(
+        &self,
+        event: &BlockSubmitted,
+        host_block_number: u64,
+    ) -> Result<(), BlockValidationError> {
+        self.validate_against_header(&event.to_header(U256::from(host_block_number)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{header, tx};
+    use crate::Alloy2718Coder;
+
+    fn block() -> ZenithBlock {
+        ZenithBlock::new(header(), vec![tx(0, 10), tx(1, 20)])
+    }
+
+    #[test]
+    fn valid() {
+        let block = block();
+        let mut committed = header();
+        committed.blockDataHash = block.block_data_hash();
+
+        let decoded =
+            ZenithBlock::<Alloy2718Coder>::from_header_and_data(committed, block.encoded_txns());
+        decoded.validate().unwrap();
+        decoded.validate_against_header(&committed).unwrap();
+
+        let event = BlockSubmitted {
+            sequencer: Address::repeat_byte(9),
+            rollupChainId: committed.rollupChainId,
+            gasLimit: committed.gasLimit,
+            rewardAddress: committed.rewardAddress,
+            blockDataHash: committed.blockDataHash,
+        };
+        decoded.validate_against_submitted(&event, committed.host_block_number()).unwrap();
+    }
+
+    #[test]
+    fn invalid() {
+        let block = block();
+        let data_hash = block.block_data_hash();
+
+        assert_eq!(
+            block.validate(),
+            Err(BlockValidationError::DataHash { expected: B256::ZERO, actual: data_hash })
+        );
+
+        let mut committed = header();
+        committed.blockDataHash = data_hash;
+        committed.hostBlockNumber = U256::from(101);
+        assert_eq!(
+            block.validate_against_header(&committed),
+            Err(BlockValidationError::HostBlockNumber {
+                expected: U256::from(101),
+                actual: U256::from(100)
+            })
+        );
+
+        committed.rollupChainId = U256::from(1);
+        assert!(matches!(
+            block.validate_against_header(&committed),
+            Err(BlockValidationError::ChainId { .. })
+        ));
+    }
+}
@@ -0,0 +1,26 @@
+use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
+
+/// The addresses of the Zenith contracts deployed on the host chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostContracts {
+    /// The [`Zenith`](crate::Zenith) contract.
+    pub zenith: Address,
+    /// The [`Passage`](crate::Passage) contract.
+    pub passage: Address,
+    /// The [`Transactor`](crate::Transactor) contract.
+    pub transactor: Address,
+    /// The [`HostOrders`](crate::HostOrders) contract.
+    pub orders: Address,
+}
+
+/// The addresses of the Zenith contracts deployed on the rollup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollupContracts {
+    /// The [`RollupPassage`](crate::RollupPassage) contract.
+    pub passage: Address,
+    /// The [`RollupOrders`](crate::RollupOrders) contract.
+    pub orders: Address,
+}
@@ -0,0 +1,255 @@
+use crate::{
+    HostContracts, HostOrders, LogMeta, Passage, RollupContracts, RollupOrders, RollupPassage,
+    Transactor, Zenith,
+};
+use alloy::primitives::{Address, Log as PrimitiveLog};
+use alloy::rpc::types::Log;
+use alloy::sol_types::SolEventInterface;
+
+/// Errors produced by a [`ZenithEventDecoder`].
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum LogDecodeError {
+    /// The log was not emitted by a configured contract.
+    #[error("log emitted by unknown address {0}")]
+    UnknownAddress(Address),
+    /// The address is configured on both the host and the rollup, so the
+    /// emitting contract cannot be determined from the log alone. Use
+    /// [`ZenithEventDecoder::decode_host`] or
+    /// [`ZenithEventDecoder::decode_rollup`] instead.
+    #[error("address {0} is configured on both the host and the rollup")]
+    AmbiguousAddress(Address),
+    /// The log is pending, and is missing its block or transaction metadata.
+    #[error("log is missing block or transaction metadata")]
+    MissingMetadata,
+    /// The log is not an event of the contract at its address.
+    #[error("undecodable log from {address}: {source}")]
+    Undecodable {
+        /// The address that emitted the log.
+        address: Address,
+        /// The decoding error.
+        #[source]
+        source: Box<alloy::sol_types::Error>,
+    },
+}
+
+/// An event emitted by any of the Zenith contracts, on the host or the
+/// rollup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZenithEvent {
+    /// An event of the [`Zenith`] contract.
+    Zenith(Zenith::ZenithEvents),
+    /// An event of the [`Passage`] contract.
+    Passage(Passage::PassageEvents),
+    /// An event of the [`Transactor`] contract.
+    Transactor(Transactor::TransactorEvents),
+    /// An event of the [`HostOrders`] contract.
+    HostOrders(HostOrders::HostOrdersEvents),
+    /// An event of the [`RollupPassage`] contract.
+    RollupPassage(RollupPassage::RollupPassageEvents),
+    /// An event of the [`RollupOrders`] contract.
+    RollupOrders(RollupOrders::RollupOrdersEvents),
+}
+
+impl ZenithEvent {
+    /// True if the event was emitted on the host chain.
+    pub const fn is_host(&self) -> bool {
+        matches!(
+            self,
+            Self::Zenith(_) | Self::Passage(_) | Self::Transactor(_) | Self::HostOrders(_)
+        )
+    }
+}
+
+/// A [`ZenithEvent`] and the position of its log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZenithLog {
+    /// The address that emitted the log.
+    pub address: Address,
+    /// The position of the log.
+    pub meta: LogMeta,
+    /// The event.
+    pub event: ZenithEvent,
+}
+
+/// Decodes logs emitted by the Zenith contracts into [`ZenithEvent`]s, using
+/// the deployed addresses of the contracts to determine which emitted each
+/// log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZenithEventDecoder {
+    host: HostContracts,
+    rollup: RollupContracts,
+}
+
+impl ZenithEventDecoder {
+    /// Create a decoder for the given deployments.
+    pub const fn new(host: HostContracts, rollup: RollupContracts) -> Self {
+        Self { host, rollup }
+    }
+
+    /// The host contracts.
+    pub const fn host(&self) -> &HostContracts {
+        &self.host
+    }
+
+    /// The rollup contracts.
+    pub const fn rollup(&self) -> &RollupContracts {
+        &self.rollup
+    }
+
+    fn is_host(&self, address: Address) -> bool {
+        let HostContracts { zenith, passage, transactor, orders } = self.host;
+        [zenith, passage, transactor, orders].contains(&address)
+    }
+
+    fn is_rollup(&self, address: Address) -> bool {
+        let RollupContracts { passage, orders } = self.rollup;
+        [passage, orders].contains(&address)
+    }
+
+    /// Decode a log from either chain. Fails if the address is configured on
+    /// both chains.
+    pub fn decode(&self, log: &Log) -> Result<ZenithLog, LogDecodeError> {
+        let address = log.address();
+        match (self.is_host(address), self.is_rollup(address)) {
+            (true, true) => Err(LogDecodeError::AmbiguousAddress(address)),
+            (true, false) => self.decode_host(log),
+            (false, true) => self.decode_rollup(log),
+            (false, false) => Err(LogDecodeError::UnknownAddress(address)),
+        }
+    }
+
+    /// Decode a log from the host chain.
+    pub fn decode_host(&self, log: &Log) -> Result<ZenithLog, LogDecodeError> {
+        with_meta(log, decode_host(&self.host, &log.inner))
+    }
+
+    /// Decode a log from the rollup.
+    pub fn decode_rollup(&self, log: &Log) -> Result<ZenithLog, LogDecodeError> {
+        let address = log.address();
+        let event = if address == self.rollup.passage {
+            decode(&log.inner).map(ZenithEvent::RollupPassage)
+        } else if address == self.rollup.orders {
+            decode(&log.inner).map(ZenithEvent::RollupOrders)
+        } else {
+            return Err(LogDecodeError::UnknownAddress(address));
+        };
+        with_meta(log, event)
+    }
+}
+
+/// Decode a log emitted by one of the host `contracts`.
+pub(crate) fn decode_host(
+    contracts: &HostContracts,
+    log: &PrimitiveLog,
+) -> Result<ZenithEvent, LogDecodeError> {
+    let address = log.address;
+    if address == contracts.zenith {
+        decode(log).map(ZenithEvent::Zenith)
+    } else if address == contracts.passage {
+        decode(log).map(ZenithEvent::Passage)
+    } else if address == contracts.transactor {
+        decode(log).map(ZenithEvent::Transactor)
+    } else if address == contracts.orders {
+        decode(log).map(ZenithEvent::HostOrders)
+    } else {
+        Err(LogDecodeError::UnknownAddress(address))
+    }
+}
+
+fn decode<E: SolEventInterface>(log: &PrimitiveLog) -> Result<E, LogDecodeError> {
+    E::decode_raw_log(log.topics(), &log.data.data, true).map_err(|source| {
+        LogDecodeError::Undecodable { address: log.address, source: Box::new(source) }
+    })
+}
+
+fn with_meta(
+    log: &Log,
+    event: Result<ZenithEvent, LogDecodeError>,
+) -> Result<ZenithLog, LogDecodeError> {
+    let event = event?;
+    let meta = LogMeta::from_rpc_log(log).ok_or(LogDecodeError::MissingMetadata)?;
+    Ok(ZenithLog { address: log.address(), meta, event })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::host_contracts;
+    use alloy::primitives::{Bytes, LogData, B256, U256};
+    use alloy::sol_types::SolEvent;
+
+    fn decoder() -> ZenithEventDecoder {
+        // the rollup orders share an address with the host orders
+        ZenithEventDecoder::new(
+            host_contracts(),
+            RollupContracts { passage: Address::repeat_byte(5), orders: Address::repeat_byte(4) },
+        )
+    }
+
+    fn log(address: Address, data: LogData) -> Log {
+        Log {
+            inner: PrimitiveLog { address, data },
+            block_hash: Some(B256::repeat_byte(0xbb)),
+            block_number: Some(100),
+            transaction_hash: Some(B256::repeat_byte(0xcc)),
+            transaction_index: Some(2),
+            log_index: Some(7),
+            ..Default::default()
+        }
+    }
+
+    fn exit() -> RollupPassage::Exit {
+        RollupPassage::Exit { hostRecipient: Address::ZERO, amount: U256::from(1) }
+    }
+
+    #[test]
+    fn decode() {
+        let d = decoder();
+        let set = Zenith::SequencerSet { sequencer: Address::ZERO, permissioned: true };
+
+        let decoded = d.decode(&log(Address::repeat_byte(1), set.encode_log_data())).unwrap();
+        assert_eq!(decoded.event, ZenithEvent::Zenith(Zenith::ZenithEvents::SequencerSet(set)));
+        assert_eq!((decoded.meta.block_number, decoded.meta.log_index), (100, 7));
+        assert!(decoded.event.is_host());
+
+        let decoded = d.decode(&log(Address::repeat_byte(5), exit().encode_log_data())).unwrap();
+        assert_eq!(
+            decoded.event,
+            ZenithEvent::RollupPassage(RollupPassage::RollupPassageEvents::Exit(exit()))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let d = decoder();
+        let data = exit().encode_log_data();
+
+        assert!(matches!(
+            d.decode(&log(Address::repeat_byte(9), data.clone())),
+            Err(LogDecodeError::UnknownAddress(a)) if a == Address::repeat_byte(9)
+        ));
+        assert!(matches!(
+            d.decode(&log(Address::repeat_byte(4), data.clone())),
+            Err(LogDecodeError::AmbiguousAddress(_))
+        ));
+        // an exit is not a host Passage event
+        assert!(matches!(
+            d.decode(&log(Address::repeat_byte(2), data.clone())),
+            Err(LogDecodeError::Undecodable { .. })
+        ));
+        assert!(matches!(
+            d.decode_rollup(&log(Address::repeat_byte(2), data.clone())),
+            Err(LogDecodeError::UnknownAddress(_))
+        ));
+
+        let mut pending = log(Address::repeat_byte(5), data.clone());
+        pending.log_index = None;
+        assert!(matches!(d.decode(&pending), Err(LogDecodeError::MissingMetadata)));
+
+        let truncated = LogData::new_unchecked(data.topics().to_vec(), Bytes::new());
+        assert!(matches!(
+            d.decode(&log(Address::repeat_byte(5), truncated)),
+            Err(LogDecodeError::Undecodable { .. })
+        ));
+    }
+}
@@ -0,0 +1,448 @@
+use super::decoder::decode_host;
+use crate::Zenith::BlockHeader as ZenithHeader;
+use crate::{
+    AggregateOrders, HostContracts, HostOrders, LogDecodeError, LogMeta, Passage, Transactor,
+    Zenith, ZenithEvent,
+};
+use alloy::consensus::{Header, Sealed};
+use alloy::primitives::{Address, B256, U256};
+use alloy::rpc::types::TransactionReceipt;
+use alloy::sol_types::SolEvent;
+
+/// Error produced when a log emitted by a Zenith contract cannot be decoded.
+#[derive(Debug, thiserror::Error)]
+#[error("failed to decode log {}: {source}", meta.log_index)]
+pub struct ExtractError {
+    /// The position of the log.
+    pub meta: LogMeta,
+    /// The decoding error.
+    #[source]
+    pub source: Box<alloy::sol_types::Error>,
+}
+
+/// An event relevant to rollup derivation, emitted by a Zenith contract on
+/// the host chain.
+///
+/// This is a projection of the host variants of [`ZenithEvent`]. See
+/// [`HostEvent::from_zenith_event`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostEvent {
+    /// A rollup block was submitted to the [`Zenith`] contract.
+    BlockSubmitted(Zenith::BlockSubmitted),
+    /// Ether entered the rollup via the [`Passage`] contract.
+    Enter(Passage::Enter),
+    /// A token entered the rollup via the [`Passage`] contract.
+    EnterToken(Passage::EnterToken),
+    /// A rollup transaction was forced via the [`Transactor`] contract.
+    Transact(Transactor::Transact),
+    /// Orders were filled via the [`HostOrders`] contract.
+    Filled(HostOrders::Filled),
+}
+
+impl HostEvent {
+    /// The rollup chain id of the event. Fills may be for any chain, so
+    /// they have none.
+    pub const fn rollup_chain_id(&self) -> Option<u64> {
+        match self {
+            Self::BlockSubmitted(event) => Some(event.rollup_chain_id()),
+            Self::Enter(event) => Some(event.rollup_chain_id()),
+            Self::EnterToken(event) => Some(event.rollup_chain_id()),
+            Self::Transact(event) => Some(event.rollup_chain_id()),
+            Self::Filled(_) => None,
+        }
+    }
+
+    /// True if the event is an [`Enter`](Self::Enter) or
+    /// [`EnterToken`](Self::EnterToken).
+    pub const fn is_enter(&self) -> bool {
+        matches!(self, Self::Enter(_) | Self::EnterToken(_))
+    }
+
+    /// Project a [`ZenithEvent`] onto the events relevant to rollup
+    /// derivation. Returns `None` for other events.
+    pub fn from_zenith_event(event: ZenithEvent) -> Option<Self> {
+        use HostOrders::HostOrdersEvents as O;
+        use Passage::PassageEvents as P;
+        use Transactor::TransactorEvents as T;
+        use Zenith::ZenithEvents as Z;
+
+        match event {
+            ZenithEvent::Zenith(Z::BlockSubmitted(event)) => Some(Self::BlockSubmitted(event)),
+            ZenithEvent::Passage(P::Enter(event)) => Some(Self::Enter(event)),
+            ZenithEvent::Passage(P::EnterToken(event)) => Some(Self::EnterToken(event)),
+            ZenithEvent::Transactor(T::Transact(event)) => Some(Self::Transact(event)),
+            ZenithEvent::HostOrders(O::Filled(event)) => Some(Self::Filled(event)),
+            _ => None,
+        }
+    }
+
+    /// The signature hashes of the events emitted by the contract at
+    /// `address`.
+    fn signatures(contracts: &HostContracts, address: Address) -> &'static [B256] {
+        if address == contracts.zenith {
+            &[Zenith::BlockSubmitted::SIGNATURE_HASH]
+        } else if address == contracts.passage {
+            &[Passage::Enter::SIGNATURE_HASH, Passage::EnterToken::SIGNATURE_HASH]
+        } else if address == contracts.transactor {
+            &[Transactor::Transact::SIGNATURE_HASH]
+        } else if address == contracts.orders {
+            &[HostOrders::Filled::SIGNATURE_HASH]
+        } else {
+            &[]
+        }
+    }
+
+    /// Decode the event from a log emitted by one of the `contracts`.
+    /// Returns `None` if the log is not a [`HostEvent`] of the contract that
+    /// emitted it.
+    fn decode(
+        contracts: &HostContracts,
+        log: &alloy::primitives::Log,
+    ) -> Option<Result<Self, Box<alloy::sol_types::Error>>> {
+        let signatures = Self::signatures(contracts, log.address);
+        if !log.topics().first().is_some_and(|topic| signatures.contains(topic)) {
+            return None;
+        }
+        match decode_host(contracts, log) {
+            Ok(event) => Self::from_zenith_event(event).map(Ok),
+            Err(LogDecodeError::Undecodable { source, .. }) => Some(Err(source)),
+            Err(_) => None,
+        }
+    }
+}
+
+impl From<HostEvent> for ZenithEvent {
+    fn from(event: HostEvent) -> Self {
+        use HostOrders::HostOrdersEvents as O;
+        use Passage::PassageEvents as P;
+        use Transactor::TransactorEvents as T;
+        use Zenith::ZenithEvents as Z;
+
+        match event {
+            HostEvent::BlockSubmitted(event) => Self::Zenith(Z::BlockSubmitted(event)),
+            HostEvent::Enter(event) => Self::Passage(P::Enter(event)),
+            HostEvent::EnterToken(event) => Self::Passage(P::EnterToken(event)),
+            HostEvent::Transact(event) => Self::Transactor(T::Transact(event)),
+            HostEvent::Filled(event) => Self::HostOrders(O::Filled(event)),
+        }
+    }
+}
+
+/// A [`HostEvent`] and the position of its log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostLog {
+    /// The position of the log.
+    pub meta: LogMeta,
+    /// The event.
+    pub event: HostEvent,
+}
+
+/// The events relevant to rollup derivation in a single host block.
+///
+/// Events are kept in log order. Logs from addresses other than the
+/// [`HostContracts`], and other events of those contracts, are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostBlockExtract {
+    number: u64,
+    hash: B256,
+    parent_hash: B256,
+    timestamp: u64,
+    events: Vec<HostLog>,
+}
+
+impl HostBlockExtract {
+    /// Extract the events from the receipts of a host block, which must be
+    /// all of the block's receipts, in order.
+    ///
+    /// Log and transaction indices are computed from the position of each
+    /// log in the receipts. Fails if a log with the signature of a
+    /// [`HostEvent`] cannot be decoded.
+    pub fn from_receipts<'a>(
+        contracts: &HostContracts,
+        header: &Sealed<Header>,
+        receipts: impl IntoIterator<Item = &'a TransactionReceipt>,
+    ) -> Result<Self, ExtractError> {
+        let mut events = Vec::new();
+        let mut log_index = 0;
+        for (tx_index, receipt) in receipts.into_iter().enumerate() {
+            for log in receipt.inner.logs() {
+                let meta = LogMeta {
+                    block_number: header.number,
+                    block_hash: header.hash(),
+                    tx_hash: receipt.transaction_hash,
+                    tx_index: tx_index as u64,
+                    log_index,
+                };
+                log_index += 1;
+                match HostEvent::decode(contracts, &log.inner) {
+                    Some(Ok(event)) => events.push(HostLog { meta, event }),
+                    Some(Err(source)) => return Err(ExtractError { meta, source }),
+                    None => {}
+                }
+            }
+        }
+        Ok(Self::new(header, events))
+    }
+
+    /// Create an extract from events that have already been decoded. The
+    /// events must be in log order.
+    pub fn new(header: &Sealed<Header>, events: Vec<HostLog>) -> Self {
+        Self {
+            number: header.number,
+            hash: header.hash(),
+            parent_hash: header.parent_hash,
+            timestamp: header.timestamp,
+            events,
+        }
+    }
+
+    /// The number of the host block.
+    pub const fn number(&self) -> u64 {
+        self.number
+    }
+
+    /// The hash of the host block.
+    pub const fn hash(&self) -> B256 {
+        self.hash
+    }
+
+    /// The hash of the parent of the host block.
+    pub const fn parent_hash(&self) -> B256 {
+        self.parent_hash
+    }
+
+    /// The timestamp of the host block.
+    pub const fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// The events in the block, in log order.
+    pub fn events(&self) -> &[HostLog] {
+        &self.events
+    }
+
+    /// The events in the block for a rollup, in log order.
+    pub fn events_for(&self, rollup_chain_id: u64) -> impl Iterator<Item = &HostLog> {
+        self.events.iter().filter(move |log| log.event.rollup_chain_id() == Some(rollup_chain_id))
+    }
+
+    /// The [`Zenith::BlockSubmitted`] event for a rollup, if any. The Zenith
+    /// contract accepts at most one rollup block per host block.
+    pub fn block_submitted(
+        &self,
+        rollup_chain_id: u64,
+    ) -> Option<(&LogMeta, &Zenith::BlockSubmitted)> {
+        self.events_for(rollup_chain_id).find_map(|log| match &log.event {
+            HostEvent::BlockSubmitted(event) => Some((&log.meta, event)),
+            _ => None,
+        })
+    }
+
+    /// The header of the rollup block submitted in this block, if any.
+    pub fn submitted_header(&self, rollup_chain_id: u64) -> Option<ZenithHeader> {
+        self.block_submitted(rollup_chain_id)
+            .map(|(_, event)| event.to_header(U256::from(self.number)))
+    }
+
+    /// The [`Passage::Enter`] and [`Passage::EnterToken`] events for a
+    /// rollup, in log order.
+    pub fn enters(&self, rollup_chain_id: u64) -> impl Iterator<Item = &HostLog> {
+        self.events_for(rollup_chain_id).filter(|log| log.event.is_enter())
+    }
+
+    /// The [`Transactor::Transact`] events for a rollup, in log order.
+    pub fn transacts(
+        &self,
+        rollup_chain_id: u64,
+    ) -> impl Iterator<Item = (&LogMeta, &Transactor::Transact)> {
+        self.events_for(rollup_chain_id).filter_map(|log| match &log.event {
+            HostEvent::Transact(event) => Some((&log.meta, event)),
+            _ => None,
+        })
+    }
+
+    /// The [`HostOrders::Filled`] events, in log order.
+    pub fn fills(&self) -> impl Iterator<Item = (&LogMeta, &HostOrders::Filled)> {
+        self.events.iter().filter_map(|log| match &log.event {
+            HostEvent::Filled(event) => Some((&log.meta, event)),
+            _ => None,
+        })
+    }
+
+    /// The outputs of all fills in the block, aggregated.
+    pub fn aggregate_fills(&self) -> AggregateOrders {
+        let mut agg = AggregateOrders::new();
+        self.fills().for_each(|(_, fill)| agg.ingest_fill(fill.outputs()));
+        agg
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{host_contracts as contracts, TEST_CHAIN_ID};
+    use alloy::consensus::{Receipt, ReceiptEnvelope, ReceiptWithBloom};
+    use alloy::primitives::{Address, Bloom, Bytes, Log, LogData};
+
+    const OTHER_CHAIN_ID: u64 = 18;
+    const TOKEN: Address = Address::repeat_byte(0x70);
+    const USER: Address = Address::repeat_byte(0x75);
+
+    fn host_header() -> Sealed<Header> {
+        Sealed::new(Header { number: 100, timestamp: 1_200, ..Default::default() })
+    }
+
+    fn log(address: Address, event: &impl SolEvent) -> Log {
+        Log { address, data: event.encode_log_data() }
+    }
+
+    fn receipt(tx: u8, logs: Vec<Log>) -> TransactionReceipt {
+        let logs =
+            logs.into_iter().map(|inner| alloy::rpc::types::Log { inner, ..Default::default() });
+        TransactionReceipt {
+            inner: ReceiptEnvelope::Eip1559(ReceiptWithBloom {
+                receipt: Receipt {
+                    status: true.into(),
+                    cumulative_gas_used: 0,
+                    logs: logs.collect(),
+                },
+                logs_bloom: Bloom::ZERO,
+            }),
+            transaction_hash: B256::repeat_byte(tx),
+            transaction_index: None,
+            block_hash: None,
+            block_number: None,
+            gas_used: 0,
+            effective_gas_price: 0,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            from: USER,
+            to: None,
+            contract_address: None,
+        }
+    }
+
+    fn submitted(chain_id: u64) -> Zenith::BlockSubmitted {
+        Zenith::BlockSubmitted {
+            sequencer: USER,
+            rollupChainId: U256::from(chain_id),
+            gasLimit: U256::from(30_000_000),
+            rewardAddress: USER,
+            blockDataHash: B256::repeat_byte(0xbd),
+        }
+    }
+
+    fn enter(chain_id: u64, amount: u64) -> Passage::Enter {
+        Passage::Enter {
+            rollupChainId: U256::from(chain_id),
+            rollupRecipient: USER,
+            amount: U256::from(amount),
+        }
+    }
+
+    fn fill(amount: u64) -> HostOrders::Filled {
+        HostOrders::Filled {
+            outputs: vec![HostOrders::Output {
+                token: TOKEN,
+                amount: U256::from(amount),
+                recipient: USER,
+                chainId: TEST_CHAIN_ID as u32,
+            }],
+        }
+    }
+
+    fn receipts() -> Vec<TransactionReceipt> {
+        let c = contracts();
+        let enter_token = Passage::EnterToken {
+            rollupChainId: U256::from(TEST_CHAIN_ID),
+            token: TOKEN,
+            rollupRecipient: USER,
+            amount: U256::from(7),
+        };
+        vec![
+            receipt(1, vec![log(c.passage, &enter(TEST_CHAIN_ID, 5)), log(c.orders, &fill(3))]),
+            // events from other addresses are ignored
+            receipt(2, vec![log(USER, &enter(TEST_CHAIN_ID, 1)), log(c.orders, &fill(4))]),
+            receipt(
+                3,
+                vec![
+                    log(c.zenith, &submitted(OTHER_CHAIN_ID)),
+                    log(c.zenith, &submitted(TEST_CHAIN_ID)),
+                ],
+            ),
+            receipt(
+                4,
+                vec![log(c.passage, &enter_token), log(c.passage, &enter(OTHER_CHAIN_ID, 9))],
+            ),
+        ]
+    }
+
+    #[test]
+    fn extract() {
+        let header = host_header();
+        let extract = HostBlockExtract::from_receipts(&contracts(), &header, &receipts()).unwrap();
+
+        assert_eq!(extract.number(), 100);
+        assert_eq!(extract.hash(), header.hash());
+        assert_eq!(extract.events().len(), 7);
+
+        let indices: Vec<_> = extract.events().iter().map(|log| log.meta.log_index).collect();
+        assert_eq!(indices, [0, 1, 3, 4, 5, 6, 7]);
+        assert_eq!(extract.events()[2].meta.tx_index, 1);
+        assert_eq!(extract.events()[2].meta.tx_hash, B256::repeat_byte(2));
+
+        let (meta, _) = extract.block_submitted(TEST_CHAIN_ID).unwrap();
+        assert_eq!(meta.log_index, 5);
+        let header = extract.submitted_header(TEST_CHAIN_ID).unwrap();
+        assert_eq!(header.host_block_number(), 100);
+        assert_eq!(header.block_data_hash(), B256::repeat_byte(0xbd));
+        assert!(extract.submitted_header(19).is_none());
+
+        let enters: Vec<_> = extract.enters(TEST_CHAIN_ID).map(|log| log.meta.log_index).collect();
+        assert_eq!(enters, [0, 6]);
+        assert_eq!(extract.enters(OTHER_CHAIN_ID).count(), 1);
+        assert_eq!(extract.transacts(TEST_CHAIN_ID).count(), 0);
+
+        let fills = extract.aggregate_fills();
+        assert_eq!(fills.outputs[&(TEST_CHAIN_ID, TOKEN)][&USER], U256::from(7));
+    }
+
+    #[test]
+    fn projection() {
+        let event = HostEvent::Enter(enter(TEST_CHAIN_ID, 5));
+        assert_eq!(HostEvent::from_zenith_event(event.clone().into()), Some(event));
+
+        // other events of the host contracts are not derivation events
+        let set = Zenith::SequencerSet { sequencer: USER, permissioned: true };
+        assert_eq!(
+            HostEvent::from_zenith_event(ZenithEvent::Zenith(Zenith::ZenithEvents::SequencerSet(
+                set
+            ))),
+            None
+        );
+        let receipts = vec![receipt(1, vec![log(contracts().zenith, &set)])];
+        let extract =
+            HostBlockExtract::from_receipts(&contracts(), &host_header(), &receipts).unwrap();
+        assert!(extract.events().is_empty());
+    }
+
+    #[test]
+    fn undecodable() {
+        let c = contracts();
+        let mut bad = log(c.passage, &enter(TEST_CHAIN_ID, 5));
+        bad.data = LogData::new_unchecked(bad.data.topics().to_vec(), Bytes::from_static(&[1]));
+        let receipts = vec![receipt(1, vec![log(c.orders, &fill(3)), bad])];
+
+        let err = HostBlockExtract::from_receipts(&c, &host_header(), &receipts).unwrap_err();
+        assert_eq!(err.meta.log_index, 1);
+    }
+
+    #[test]
+    fn foreign_signature() {
+        // an event of another configured contract is ignored, not decoded
+        let c = contracts();
+        let receipts = vec![receipt(1, vec![log(c.zenith, &enter(TEST_CHAIN_ID, 5))])];
+        let extract = HostBlockExtract::from_receipts(&c, &host_header(), &receipts).unwrap();
+        assert!(extract.events().is_empty());
+    }
+}
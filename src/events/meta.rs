@@ -0,0 +1,35 @@
+use alloy::primitives::B256;
+use serde::{Deserialize, Serialize};
+
+/// The position of a log in the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogMeta {
+    /// The number of the block containing the log.
+    pub block_number: u64,
+    /// The hash of the block containing the log.
+    pub block_hash: B256,
+    /// The hash of the transaction that emitted the log.
+    pub tx_hash: B256,
+    /// The index of the transaction in the block.
+    pub tx_index: u64,
+    /// The index of the log in the block.
+    pub log_index: u64,
+}
+
+impl LogMeta {
+    /// Get the position of an RPC log. Returns `None` if the log is pending,
+    /// and so is missing any of the fields.
+    pub const fn from_rpc_log(log: &alloy::rpc::types::Log) -> Option<Self> {
+        let (Some(block_number), Some(block_hash), Some(tx_hash), Some(tx_index), Some(log_index)) = (
+            log.block_number,
+            log.block_hash,
+            log.transaction_hash,
+            log.transaction_index,
+            log.log_index,
+        ) else {
+            return None;
+        };
+        Some(Self { block_number, block_hash, tx_hash, tx_index, log_index })
+    }
+}
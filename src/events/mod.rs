@@ -0,0 +1,20 @@
+mod contracts;
+pub use contracts::{HostContracts, RollupContracts};
+
+mod decoder;
+pub use decoder::{LogDecodeError, ZenithEvent, ZenithEventDecoder, ZenithLog};
+
+mod extract;
+pub use extract::{ExtractError, HostBlockExtract, HostEvent, HostLog};
+
+mod meta;
+pub use meta::LogMeta;
+
+mod stream;
+pub use stream::{
+    HostEventStream, HostLogBatch, SkippedLog, StreamCheckpoint, StreamError, DEFAULT_BATCH_SIZE,
+    DEFAULT_POLL_INTERVAL,
+};
+
+mod tracker;
+pub use tracker::{HostChainTracker, TrackerError, TrackerEvent, DEFAULT_TRACKER_DEPTH};
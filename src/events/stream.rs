@@ -0,0 +1,404 @@
+use super::decoder::decode_host;
+use crate::{HostContracts, HostEvent, HostLog, LogDecodeError, LogMeta, ZenithLog};
+use alloy::providers::Provider;
+use alloy::rpc::types::{Filter, Log};
+use alloy::transports::TransportError;
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The default number of host blocks fetched per request by a
+/// [`HostEventStream`].
+pub const DEFAULT_BATCH_SIZE: u64 = 1_000;
+
+/// The default interval at which a [`HostEventStream`] polls for new blocks
+/// once it has caught up.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Errors produced by a [`HostEventStream`].
+#[derive(Debug, thiserror::Error)]
+pub enum StreamError {
+    /// An RPC request failed.
+    #[error(transparent)]
+    Rpc(#[from] TransportError),
+}
+
+/// The position from which a [`HostEventStream`] resumes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamCheckpoint {
+    /// The next host block to fetch.
+    pub next_block: u64,
+}
+
+/// A log that a [`HostEventStream`] could not decode, or that is missing its
+/// block or transaction metadata.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedLog {
+    /// The log.
+    pub log: Log,
+    /// Why the log was skipped.
+    pub error: LogDecodeError,
+}
+
+/// The events in a range of host blocks, yielded by a [`HostEventStream`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostLogBatch {
+    /// The first block in the range.
+    pub from_block: u64,
+    /// The last block in the range, inclusive.
+    pub to_block: u64,
+    /// The events in the range, in log order.
+    pub events: Vec<ZenithLog>,
+    /// The logs in the range that were skipped, in log order.
+    pub skipped: Vec<SkippedLog>,
+}
+
+impl HostLogBatch {
+    /// The checkpoint from which to resume after this batch.
+    pub const fn checkpoint(&self) -> StreamCheckpoint {
+        StreamCheckpoint { next_block: self.to_block + 1 }
+    }
+
+    /// The events relevant to rollup derivation, in log order. See
+    /// [`HostEvent::from_zenith_event`].
+    pub fn host_events(&self) -> impl Iterator<Item = HostLog> + '_ {
+        self.events.iter().filter_map(|log| {
+            let event = HostEvent::from_zenith_event(log.event.clone())?;
+            Some(HostLog { meta: log.meta, event })
+        })
+    }
+}
+
+/// A feed of all events emitted by the [`HostContracts`], read from a
+/// [`Provider`] and decoded as [`ZenithEvent`]s. See
+/// [`HostLogBatch::host_events`] for the events relevant to derivation.
+///
+/// The stream backfills from its starting block to the latest block in
+/// batches of at most `batch_size` blocks, and then follows new blocks,
+/// polling every `poll_interval`. With an end block set, the stream ends
+/// after that block instead of following.
+///
+/// Each batch covers a complete range of blocks, so the stream can be
+/// resumed from [`HostLogBatch::checkpoint`] without missing or repeating
+/// events. A log that cannot be decoded does not stop the stream. It is
+/// reported in [`HostLogBatch::skipped`] instead. Logs are read as soon as
+/// their block is the latest, so reorgs are not handled. See
+/// [`HostChainTracker`] to track them.
+///
+/// [`HostChainTracker`]: crate::HostChainTracker
+/// [`ZenithEvent`]: crate::ZenithEvent
+#[derive(Debug, Clone)]
+pub struct HostEventStream<P> {
+    provider: P,
+    contracts: HostContracts,
+    next_block: u64,
+    end_block: Option<u64>,
+    batch_size: u64,
+    poll_interval: Duration,
+}
+
+impl<P> HostEventStream<P>
+where
+    P: Provider,
+{
+    /// Create a stream starting at `from_block`.
+    pub const fn new(provider: P, contracts: HostContracts, from_block: u64) -> Self {
+        Self {
+            provider,
+            contracts,
+            next_block: from_block,
+            end_block: None,
+            batch_size: DEFAULT_BATCH_SIZE,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Create a stream resuming from a checkpoint.
+    pub const fn from_checkpoint(
+        provider: P,
+        contracts: HostContracts,
+        checkpoint: StreamCheckpoint,
+    ) -> Self {
+        Self::new(provider, contracts, checkpoint.next_block)
+    }
+
+    /// Set the maximum number of blocks fetched per request. The batch size
+    /// is at least one.
+    pub const fn with_batch_size(mut self, batch_size: u64) -> Self {
+        self.batch_size = if batch_size == 0 { 1 } else { batch_size };
+        self
+    }
+
+    /// Set the interval at which to poll for new blocks.
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// End the stream after the given block, instead of following new
+    /// blocks.
+    pub const fn with_end_block(mut self, end_block: u64) -> Self {
+        self.end_block = Some(end_block);
+        self
+    }
+
+    /// The checkpoint from which to resume the stream.
+    pub const fn checkpoint(&self) -> StreamCheckpoint {
+        StreamCheckpoint { next_block: self.next_block }
+    }
+
+    /// Fetch the next batch of events, waiting for new blocks if the stream
+    /// has caught up. Returns `None` once the end block has been fetched.
+    pub async fn next_batch(&mut self) -> Result<Option<HostLogBatch>, StreamError> {
+        let latest = loop {
+            if self.end_block.is_some_and(|end| self.next_block > end) {
+                return Ok(None);
+            }
+            let latest = self.provider.get_block_number().await?;
+            if latest >= self.next_block {
+                break latest;
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        };
+
+        let from_block = self.next_block;
+        let mut to_block = latest.min(from_block.saturating_add(self.batch_size - 1));
+        if let Some(end) = self.end_block {
+            to_block = to_block.min(end);
+        }
+
+        let filter = Filter::new()
+            .address(vec![
+                self.contracts.zenith,
+                self.contracts.passage,
+                self.contracts.transactor,
+                self.contracts.orders,
+            ])
+            .from_block(from_block)
+            .to_block(to_block);
+        let logs = self.provider.get_logs(&filter).await?;
+
+        let mut events = Vec::with_capacity(logs.len());
+        let mut skipped = Vec::new();
+        for log in logs {
+            match self.decode(&log) {
+                Ok(event) => events.push(event),
+                Err(error) => skipped.push(SkippedLog { log, error }),
+            }
+        }
+
+        self.next_block = to_block + 1;
+        Ok(Some(HostLogBatch { from_block, to_block, events, skipped }))
+    }
+
+    fn decode(&self, log: &Log) -> Result<ZenithLog, LogDecodeError> {
+        let event = decode_host(&self.contracts, &log.inner)?;
+        let meta = LogMeta::from_rpc_log(log).ok_or(LogDecodeError::MissingMetadata)?;
+        Ok(ZenithLog { address: log.address(), meta, event })
+    }
+
+    /// Convert into a [`Stream`] of batches. The stream ends after the end
+    /// block, or after the first error.
+    pub fn into_stream(self) -> impl Stream<Item = Result<HostLogBatch, StreamError>> {
+        futures_util::stream::try_unfold(self, |mut stream| async move {
+            Ok(stream.next_batch().await?.map(|batch| (batch, stream)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{host_contracts as contracts, TEST_CHAIN_ID};
+    use crate::{Passage, Zenith, ZenithEvent};
+    use alloy::primitives::{Address, LogData, B256, U256};
+    use alloy::providers::RootProvider;
+    use alloy::rpc::client::RpcClient;
+    use alloy::rpc::json_rpc::{RequestPacket, Response, ResponsePacket, ResponsePayload};
+    use alloy::sol_types::SolEvent;
+    use alloy::transports::{TransportErrorKind, TransportFut};
+    use futures_util::{StreamExt, TryStreamExt};
+    use serde_json::{json, value::to_raw_value, Value};
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll};
+
+    /// A transport that answers requests with queued responses, and records
+    /// the requests it receives.
+    #[derive(Debug, Clone, Default)]
+    struct MockTransport {
+        responses: Arc<Mutex<VecDeque<Value>>>,
+        requests: Arc<Mutex<Vec<(String, Value)>>>,
+    }
+
+    impl MockTransport {
+        fn push(&self, response: impl Serialize) {
+            self.responses.lock().unwrap().push_back(serde_json::to_value(response).unwrap());
+        }
+
+        fn methods(&self) -> Vec<String> {
+            self.requests.lock().unwrap().iter().map(|(m, _)| m.clone()).collect()
+        }
+
+        fn params(&self, i: usize) -> Value {
+            self.requests.lock().unwrap()[i].1.clone()
+        }
+    }
+
+    impl tower::Service<RequestPacket> for MockTransport {
+        type Response = ResponsePacket;
+        type Error = TransportError;
+        type Future = TransportFut<'static>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: RequestPacket) -> Self::Future {
+            let RequestPacket::Single(req) = req else {
+                return Box::pin(async {
+                    Err(TransportErrorKind::custom_str("batch requests unsupported"))
+                });
+            };
+            let params =
+                req.params().map_or(Value::Null, |p| serde_json::from_str(p.get()).unwrap());
+            self.requests.lock().unwrap().push((req.method().to_string(), params));
+            let Some(result) = self.responses.lock().unwrap().pop_front() else {
+                return Box::pin(async {
+                    Err(TransportErrorKind::custom_str("no queued response"))
+                });
+            };
+            let payload = ResponsePayload::Success(to_raw_value(&result).unwrap());
+            let response = Response { id: req.id().clone(), payload };
+            Box::pin(async move { Ok(ResponsePacket::Single(response)) })
+        }
+    }
+
+    fn provider() -> (RootProvider, MockTransport) {
+        let transport = MockTransport::default();
+        (RootProvider::new(RpcClient::new(transport.clone(), true)), transport)
+    }
+
+    fn enter_log(block: u64, log_index: u64) -> Value {
+        let enter = Passage::Enter {
+            rollupChainId: U256::from(TEST_CHAIN_ID),
+            rollupRecipient: Address::ZERO,
+            amount: U256::from(block),
+        };
+        rpc_log(contracts().passage, enter.encode_log_data(), block, log_index)
+    }
+
+    fn sequencer_set_log(block: u64, log_index: u64) -> Value {
+        let set = Zenith::SequencerSet { sequencer: Address::ZERO, permissioned: true };
+        rpc_log(contracts().zenith, set.encode_log_data(), block, log_index)
+    }
+
+    fn rpc_log(address: Address, data: LogData, block: u64, log_index: u64) -> Value {
+        json!({
+            "address": address,
+            "topics": data.topics(),
+            "data": data.data,
+            "blockNumber": format!("{block:#x}"),
+            "blockHash": B256::with_last_byte(block as u8),
+            "transactionHash": B256::with_last_byte(log_index as u8),
+            "transactionIndex": "0x0",
+            "logIndex": format!("{log_index:#x}"),
+            "removed": false,
+        })
+    }
+
+    #[tokio::test]
+    async fn backfill() {
+        let (provider, transport) = provider();
+        let stream =
+            HostEventStream::new(provider, contracts(), 10).with_batch_size(5).with_end_block(17);
+
+        transport.push("0x64");
+        transport.push(vec![enter_log(11, 0), sequencer_set_log(12, 0), enter_log(14, 3)]);
+        transport.push("0x64");
+        transport.push(vec![enter_log(16, 1)]);
+
+        let batches: Vec<_> = stream.into_stream().try_collect().await.unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!((batches[0].from_block, batches[0].to_block), (10, 14));
+        assert_eq!((batches[1].from_block, batches[1].to_block), (15, 17));
+        assert_eq!(batches[1].checkpoint(), StreamCheckpoint { next_block: 18 });
+
+        // all events are yielded, and projected onto the derivation events
+        assert!(matches!(batches[0].events[1].event, ZenithEvent::Zenith(_)));
+        let host_events: Vec<_> = batches[0].host_events().collect();
+        assert_eq!(host_events.len(), 2);
+        let meta = host_events[1].meta;
+        assert_eq!((meta.block_number, meta.tx_index, meta.log_index), (14, 0, 3));
+        assert!(matches!(
+            batches[1].host_events().next().unwrap().event,
+            HostEvent::Enter(ref e) if e.amount == U256::from(16)
+        ));
+
+        assert_eq!(
+            transport.methods(),
+            ["eth_blockNumber", "eth_getLogs", "eth_blockNumber", "eth_getLogs"]
+        );
+        let filter = &transport.params(1)[0];
+        assert_eq!(
+            (filter["fromBlock"].as_str(), filter["toBlock"].as_str()),
+            (Some("0xa"), Some("0xe"))
+        );
+        assert_eq!(filter["address"].as_array().unwrap().len(), 4);
+        assert_eq!(filter["topics"], json!([]));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn follow() {
+        let (provider, transport) = provider();
+        let checkpoint = StreamCheckpoint { next_block: 20 };
+        let mut stream = HostEventStream::from_checkpoint(provider, contracts(), checkpoint)
+            .with_poll_interval(Duration::from_secs(12));
+
+        // caught up at 19, then block 20 arrives
+        transport.push("0x13");
+        transport.push("0x14");
+        transport.push(vec![enter_log(20, 0)]);
+
+        let batch = stream.next_batch().await.unwrap().unwrap();
+        assert_eq!((batch.from_block, batch.to_block), (20, 20));
+        assert_eq!(batch.events.len(), 1);
+        assert_eq!(stream.checkpoint(), StreamCheckpoint { next_block: 21 });
+        assert_eq!(transport.methods(), ["eth_blockNumber", "eth_blockNumber", "eth_getLogs"]);
+    }
+
+    #[tokio::test]
+    async fn skipped() {
+        let (provider, transport) = provider();
+        let stream = HostEventStream::new(provider, contracts(), 0).with_end_block(1);
+
+        let mut pending = enter_log(0, 0);
+        pending["logIndex"] = Value::Null;
+        let mut undecodable = enter_log(0, 1);
+        undecodable["data"] = json!("0x");
+        transport.push("0x1");
+        transport.push(vec![pending, undecodable, enter_log(1, 0)]);
+
+        // the batch is yielded, and the checkpoint advances past the bad logs
+        let mut stream = Box::pin(stream.into_stream());
+        let batch = stream.next().await.unwrap().unwrap();
+        assert_eq!(batch.checkpoint(), StreamCheckpoint { next_block: 2 });
+        assert_eq!(batch.events.len(), 1);
+        assert_eq!(batch.skipped.len(), 2);
+        assert_eq!(batch.skipped[0].error, LogDecodeError::MissingMetadata);
+        assert!(matches!(batch.skipped[1].error, LogDecodeError::Undecodable { .. }));
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn rpc_error() {
+        let (provider, _transport) = provider();
+        let stream = HostEventStream::new(provider, contracts(), 0);
+
+        // no response is queued, so the request fails and the stream ends
+        let mut stream = Box::pin(stream.into_stream());
+        assert!(matches!(stream.next().await, Some(Err(StreamError::Rpc(_)))));
+        assert!(stream.next().await.is_none());
+    }
+}
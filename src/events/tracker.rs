@@ -0,0 +1,283 @@
+use crate::{HostBlockExtract, HostLog};
+use alloy::primitives::B256;
+use std::collections::VecDeque;
+
+/// The default number of host blocks kept by a [`HostChainTracker`].
+pub const DEFAULT_TRACKER_DEPTH: usize = 64;
+
+/// Errors produced when a host block cannot be added to a
+/// [`HostChainTracker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum TrackerError {
+    /// The parent of the block is not tracked. Either blocks are missing, or
+    /// the reorg is deeper than the tracked history.
+    #[error("unknown parent {parent_hash} of block {number}")]
+    UnknownParent {
+        /// The number of the block.
+        number: u64,
+        /// The parent hash of the block.
+        parent_hash: B256,
+    },
+    /// The block number is not one greater than its parent's.
+    #[error("block number mismatch: expected {expected}, got {got}")]
+    Number {
+        /// The number following the parent's.
+        expected: u64,
+        /// The block number.
+        got: u64,
+    },
+}
+
+/// A change to the events of the canonical host chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrackerEvent {
+    /// The event was removed from the canonical chain by a reorg, and its
+    /// effects should be undone.
+    Revert(HostLog),
+    /// The event was added to the canonical chain.
+    Apply(HostLog),
+}
+
+impl TrackerEvent {
+    /// The log of the event.
+    pub const fn log(&self) -> &HostLog {
+        match self {
+            Self::Revert(log) | Self::Apply(log) => log,
+        }
+    }
+
+    /// True if the event is a [`TrackerEvent::Revert`].
+    pub const fn is_revert(&self) -> bool {
+        matches!(self, Self::Revert(_))
+    }
+}
+
+/// Tracks the recent canonical host chain, and detects reorgs from the
+/// parent links of new blocks.
+///
+/// Each block is added as a [`HostBlockExtract`], which records its hash and
+/// parent hash next to its events. When a block extends a tracked block other
+/// than the tip, the blocks after that one are reverted. At most `depth`
+/// blocks are kept, so deeper reorgs cannot be detected, and are reported as
+/// [`TrackerError::UnknownParent`].
+#[derive(Debug, Clone)]
+pub struct HostChainTracker {
+    blocks: VecDeque<HostBlockExtract>,
+    depth: usize,
+}
+
+impl Default for HostChainTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_TRACKER_DEPTH)
+    }
+}
+
+impl HostChainTracker {
+    /// Create a tracker that keeps at most `depth` blocks. The depth is at
+    /// least one.
+    pub fn new(depth: usize) -> Self {
+        Self { blocks: VecDeque::new(), depth: depth.max(1) }
+    }
+
+    /// The tracked blocks, oldest first.
+    pub const fn blocks(&self) -> &VecDeque<HostBlockExtract> {
+        &self.blocks
+    }
+
+    /// The tip of the tracked chain, if any.
+    pub fn tip(&self) -> Option<&HostBlockExtract> {
+        self.blocks.back()
+    }
+
+    /// Get a tracked block by hash.
+    pub fn get(&self, hash: B256) -> Option<&HostBlockExtract> {
+        self.blocks.iter().find(|block| block.hash() == hash)
+    }
+
+    /// Add a block to the tracked chain, returning the resulting changes to
+    /// the canonical events.
+    ///
+    /// If the block's parent is not the tip, the blocks after the parent are
+    /// removed, and their events are reverted, latest first. The events of
+    /// the block are then applied, in log order. The first block added to an
+    /// empty tracker is accepted without a parent. Adding a block that is
+    /// already tracked has no effect.
+    pub fn push(&mut self, block: HostBlockExtract) -> Result<Vec<TrackerEvent>, TrackerError> {
+        if self.get(block.hash()).is_some() {
+            return Ok(Vec::new());
+        }
+
+        let mut events = Vec::new();
+        if !self.blocks.is_empty() {
+            let Some(parent) = self.blocks.iter().rposition(|b| b.hash() == block.parent_hash())
+            else {
+                return Err(TrackerError::UnknownParent {
+                    number: block.number(),
+                    parent_hash: block.parent_hash(),
+                });
+            };
+            let expected = self.blocks[parent].number() + 1;
+            if block.number() != expected {
+                return Err(TrackerError::Number { expected, got: block.number() });
+            }
+            for reverted in self.blocks.drain(parent + 1..).rev() {
+                events.extend(reverted.events().iter().rev().cloned().map(TrackerEvent::Revert));
+            }
+        }
+
+        events.extend(block.events().iter().cloned().map(TrackerEvent::Apply));
+        self.blocks.push_back(block);
+        if self.blocks.len() > self.depth {
+            self.blocks.pop_front();
+        }
+        Ok(events)
+    }
+
+    /// Remove all tracked blocks up to and including the block with the
+    /// given number, for example once it is finalized. The tip is always
+    /// kept, so that the parent of the next block is still checked.
+    pub fn prune(&mut self, number: u64) {
+        while self.blocks.len() > 1
+            && self.blocks.front().is_some_and(|block| block.number() <= number)
+        {
+            self.blocks.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::TEST_CHAIN_ID;
+    use crate::{HostEvent, LogMeta, Passage, Zenith};
+    use alloy::consensus::{Header, Sealed};
+    use alloy::primitives::{Address, U256};
+
+    /// A synthetic host block with a block submission and an enter.
+    /// `fork` distinguishes blocks at the same height.
+    fn block(parent: Option<&HostBlockExtract>, fork: u64) -> HostBlockExtract {
+        let header = Sealed::new(Header {
+            number: parent.map_or(100, |p| p.number() + 1),
+            parent_hash: parent.map_or(B256::ZERO, |p| p.hash()),
+            extra_data: fork.to_be_bytes().into(),
+            ..Default::default()
+        });
+        let meta = |log_index| LogMeta {
+            block_number: header.number,
+            block_hash: header.hash(),
+            tx_hash: B256::with_last_byte(log_index as u8),
+            tx_index: log_index,
+            log_index,
+        };
+        let submitted = HostEvent::BlockSubmitted(Zenith::BlockSubmitted {
+            sequencer: Address::ZERO,
+            rollupChainId: U256::from(TEST_CHAIN_ID),
+            gasLimit: U256::from(30_000_000),
+            rewardAddress: Address::ZERO,
+            blockDataHash: B256::repeat_byte(fork as u8),
+        });
+        let enter = HostEvent::Enter(Passage::Enter {
+            rollupChainId: U256::from(TEST_CHAIN_ID),
+            rollupRecipient: Address::ZERO,
+            amount: U256::from(fork),
+        });
+        HostBlockExtract::new(
+            &header,
+            vec![
+                HostLog { meta: meta(0), event: submitted },
+                HostLog { meta: meta(1), event: enter },
+            ],
+        )
+    }
+
+    fn applied(block: &HostBlockExtract) -> Vec<TrackerEvent> {
+        block.events().iter().cloned().map(TrackerEvent::Apply).collect()
+    }
+
+    #[test]
+    fn extend() {
+        let mut tracker = HostChainTracker::default();
+        let a = block(None, 0);
+        let b = block(Some(&a), 0);
+
+        assert_eq!(tracker.push(a.clone()).unwrap(), applied(&a));
+        assert_eq!(tracker.push(b.clone()).unwrap(), applied(&b));
+        assert_eq!(tracker.push(b.clone()).unwrap(), vec![]);
+        assert_eq!(tracker.tip(), Some(&b));
+    }
+
+    #[test]
+    fn reorg() {
+        let mut tracker = HostChainTracker::default();
+        let a = block(None, 0);
+        let b = block(Some(&a), 0);
+        let c = block(Some(&b), 0);
+        for block in [&a, &b, &c] {
+            tracker.push(block.clone()).unwrap();
+        }
+
+        let b2 = block(Some(&a), 1);
+        let events = tracker.push(b2.clone()).unwrap();
+
+        // c's enter, c's submission, b's enter, b's submission, then b2
+        let reverted: Vec<_> = events.iter().take_while(|e| e.is_revert()).collect();
+        assert_eq!(reverted.len(), 4);
+        assert_eq!(reverted[0].log(), &c.events()[1]);
+        assert_eq!(reverted[3].log(), &b.events()[0]);
+        assert_eq!(events[4..], applied(&b2));
+
+        let hashes: Vec<_> = tracker.blocks().iter().map(|b| b.hash()).collect();
+        assert_eq!(hashes, [a.hash(), b2.hash()]);
+
+        // the new chain continues from b2
+        let c2 = block(Some(&b2), 1);
+        assert_eq!(tracker.push(c2.clone()).unwrap(), applied(&c2));
+    }
+
+    #[test]
+    fn errors() {
+        let mut tracker = HostChainTracker::new(2);
+        let a = block(None, 0);
+        let b = block(Some(&a), 0);
+        let c = block(Some(&b), 0);
+        for block in [&a, &b, &c] {
+            tracker.push(block.clone()).unwrap();
+        }
+        assert_eq!(tracker.blocks().len(), 2);
+
+        // a was dropped, so a reorg to it cannot be followed
+        let b2 = block(Some(&a), 1);
+        assert_eq!(
+            tracker.push(b2),
+            Err(TrackerError::UnknownParent { number: 101, parent_hash: a.hash() })
+        );
+
+        let gap = Sealed::new(Header { number: 105, parent_hash: c.hash(), ..Default::default() });
+        let gap = HostBlockExtract::new(&gap, vec![]);
+        assert_eq!(tracker.push(gap), Err(TrackerError::Number { expected: 103, got: 105 }));
+
+        tracker.prune(101);
+        assert_eq!(tracker.blocks().len(), 1);
+    }
+
+    #[test]
+    fn prune_keeps_tip() {
+        let mut tracker = HostChainTracker::default();
+        let a = block(None, 0);
+        let b = block(Some(&a), 0);
+        tracker.push(a.clone()).unwrap();
+        tracker.push(b.clone()).unwrap();
+
+        tracker.prune(b.number());
+        assert_eq!(tracker.tip(), Some(&b));
+
+        // a block from another fork is still refused
+        let c2 = block(Some(&block(Some(&a), 1)), 1);
+        assert_eq!(
+            tracker.push(c2.clone()),
+            Err(TrackerError::UnknownParent { number: 102, parent_hash: c2.parent_hash() })
+        );
+        let c = block(Some(&b), 0);
+        assert_eq!(tracker.push(c.clone()).unwrap(), applied(&c));
+    }
+}
@@ -17,7 +17,34 @@ pub use bindings::{
 };
 
 mod block;
-pub use block::{decode_txns, encode_txns, Alloy2718Coder, Coder, ZenithBlock, ZenithTransaction};
+pub use block::{
+    blob_capacity, blobs_required, block_data_version, check_header_link, decode_txns,
+    decode_txns_strict, decode_txns_with_limits, encode_txns, Alloy2718Coder, BlobError,
+    BlockDataDecoder, BlockLimitError, BlockPacker, BlockValidationError, BundleSubmission, Coder,
+    DecodeLimitError, DecodeLimits, DecodeReport, DecoderRegistry, DecompressionError,
+    DisallowedTx, ExpandedBlock, HeaderLinkError, PackItem, PackRejection, PackedBlocks,
+    PolicyCoder, PolicyViolation, RecoverSigner, RejectedItem, RejectedTx, RollupHeader,
+    SenderRecoveryError, SubmitBlockError, SubmittedBlock, TxDecodeError, TxDecodeFailure,
+    TxInclusionProof, TxItem, TxItems, TxPolicy, TxPolicyConfig, TxProofError, UnknownVersion,
+    VersionedCoder, ZenithBlock, ZenithBlockBuilder, ZenithTransaction,
+    DEFAULT_MAX_DECOMPRESSED_SIZE, MAX_BLOCK_DATA_VERSION, MIN_TRANSACTION_GAS,
+};
+#[cfg(feature = "zstd")]
+pub use block::{ZstdCoder, ZSTD_BLOCK_DATA_VERSION, ZSTD_LEVEL};
+
+mod events;
+pub use events::{
+    ExtractError, HostBlockExtract, HostChainTracker, HostContracts, HostEvent, HostEventStream,
+    HostLog, HostLogBatch, LogDecodeError, LogMeta, RollupContracts, SkippedLog, StreamCheckpoint,
+    StreamError, TrackerError, TrackerEvent, ZenithEvent, ZenithEventDecoder, ZenithLog,
+    DEFAULT_BATCH_SIZE, DEFAULT_POLL_INTERVAL, DEFAULT_TRACKER_DEPTH,
+};
+
+mod system;
+pub use system::{
+    EnterTransactError, RollupTxCoder, RollupTxEnvelope, TxMint, TxTransact, MINT_TX_TYPE,
+    TOKEN_MINT_GAS_LIMIT, TRANSACT_TX_TYPE,
+};
 
 mod orders;
 pub use orders::{AggregateOrders, SignedOrder};
@@ -27,12 +54,21 @@ pub use bundle::{
     ZenithCallBundle, ZenithCallBundleResponse, ZenithEthBundle, ZenithEthBundleResponse,
 };
 
+mod network;
+pub use network::{NetworkConstants, NetworkError};
+
+mod revert;
+pub use revert::{Retry, ZenithRevert};
+
 mod req;
-pub use req::SignRequest;
+pub use req::{SignRequest, DOMAIN_BINDING};
 
 mod resp;
 pub use resp::SignResponse;
 
+#[cfg(test)]
+mod test_utils;
+
 use alloy::primitives::{address, Address};
 
 /// System address with permission to mint tokens on pre-deploys.
@@ -0,0 +1,282 @@
+use crate::{
+    HostContracts, Passage, RollupContracts, SignRequest, TxMint, TxPolicyConfig,
+    ZenithEventDecoder, DOMAIN_BINDING, MINTER_ADDRESS,
+};
+use alloy::consensus::TxType;
+use alloy::primitives::{Address, B256};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// Errors produced when loading or validating [`NetworkConstants`].
+#[derive(Debug, thiserror::Error)]
+pub enum NetworkError {
+    /// A chain id is zero.
+    #[error("chain id must not be zero")]
+    ZeroChainId,
+    /// The host and rollup chain ids are the same.
+    #[error("host and rollup chain ids are both {0}")]
+    SameChainId(u64),
+    /// A contract or predeploy address is zero.
+    #[error("{0} address must not be zero")]
+    ZeroAddress(&'static str),
+    /// An address is used more than once on the same chain.
+    #[error("address {0} is used more than once")]
+    DuplicateAddress(Address),
+    /// The JSON is invalid.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// The TOML is invalid.
+    #[cfg(feature = "toml")]
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+}
+
+fn default_signing_domain() -> String {
+    DOMAIN_BINDING.to_owned()
+}
+
+/// The constants of a Zenith network: its chain ids, contract deployments,
+/// and signing domain.
+///
+/// Constants are loaded from JSON or TOML, and are validated on load. See
+/// [`NetworkConstants::validate`].
+///
+/// The crate does not ship presets for known networks, as it does not track
+/// their deployments. Load the constants of a network from its published
+/// configuration instead.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkConstants {
+    /// The chain id of the host chain.
+    pub host_chain_id: u64,
+    /// The chain id of the rollup.
+    pub rollup_chain_id: u64,
+    /// The host block in which the Zenith contracts were deployed. Rollup
+    /// derivation starts from this block.
+    pub deploy_block_number: u64,
+    /// The contracts on the host chain.
+    pub host: HostContracts,
+    /// The predeployed contracts on the rollup.
+    pub rollup: RollupContracts,
+    /// The rollup token predeploys, keyed by the host token they represent.
+    #[serde(default)]
+    pub token_predeploys: BTreeMap<Address, Address>,
+    /// The domain binding of [`SignRequest`]s. Defaults to
+    /// [`DOMAIN_BINDING`].
+    #[serde(default = "default_signing_domain")]
+    pub signing_domain: String,
+}
+
+impl NetworkConstants {
+    /// Load and validate constants from JSON.
+    pub fn from_json(json: &str) -> Result<Self, NetworkError> {
+        let constants: Self = serde_json::from_str(json)?;
+        constants.validate()?;
+        Ok(constants)
+    }
+
+    /// Load and validate constants from TOML.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(toml: &str) -> Result<Self, NetworkError> {
+        let constants: Self = toml::from_str(toml)?;
+        constants.validate()?;
+        Ok(constants)
+    }
+
+    /// Check that the chain ids are non-zero and distinct, and that the
+    /// addresses are non-zero and unique on each chain. Rollup addresses
+    /// include the token predeploys and [`MINTER_ADDRESS`].
+    pub fn validate(&self) -> Result<(), NetworkError> {
+        if self.host_chain_id == 0 || self.rollup_chain_id == 0 {
+            return Err(NetworkError::ZeroChainId);
+        }
+        if self.host_chain_id == self.rollup_chain_id {
+            return Err(NetworkError::SameChainId(self.host_chain_id));
+        }
+
+        let HostContracts { zenith, passage, transactor, orders } = self.host;
+        check_unique([
+            ("zenith", zenith),
+            ("passage", passage),
+            ("transactor", transactor),
+            ("host orders", orders),
+        ])?;
+
+        let RollupContracts { passage, orders } = self.rollup;
+        check_unique(
+            [("rollup passage", passage), ("rollup orders", orders), ("minter", MINTER_ADDRESS)]
+                .into_iter()
+                .chain(self.token_predeploys.values().map(|&a| ("token predeploy", a))),
+        )?;
+        if self.token_predeploys.contains_key(&Address::ZERO) {
+            return Err(NetworkError::ZeroAddress("host token"));
+        }
+        Ok(())
+    }
+
+    /// The rollup token predeploy for a host token, if any.
+    pub fn token_predeploy(&self, host_token: Address) -> Option<Address> {
+        self.token_predeploys.get(&host_token).copied()
+    }
+
+    /// Create the mint caused by a [`Passage::EnterToken`] event, using the
+    /// token's predeploy. Returns `None` if the token has no predeploy. See
+    /// [`TxMint::from_enter_token`].
+    pub fn mint_for_enter_token(
+        &self,
+        enter: &Passage::EnterToken,
+        host_tx_hash: B256,
+        log_index: u64,
+    ) -> Option<TxMint> {
+        let predeploy = self.token_predeploy(enter.token())?;
+        Some(TxMint::from_enter_token(enter, predeploy, host_tx_hash, log_index))
+    }
+
+    /// A decoder for the network's contract events.
+    pub const fn decoder(&self) -> ZenithEventDecoder {
+        ZenithEventDecoder::new(self.host, self.rollup)
+    }
+
+    /// A policy allowing the given transaction types on the rollup chain.
+    pub fn tx_policy(&self, allowed_types: impl IntoIterator<Item = TxType>) -> TxPolicyConfig {
+        TxPolicyConfig::new(allowed_types, [self.rollup_chain_id])
+    }
+
+    /// Compute the signing hash of a [`SignRequest`] with the network's
+    /// signing domain.
+    pub fn signing_hash(&self, request: &SignRequest) -> B256 {
+        request.signing_hash_with_domain(&self.signing_domain)
+    }
+}
+
+fn check_unique(
+    addresses: impl IntoIterator<Item = (&'static str, Address)>,
+) -> Result<(), NetworkError> {
+    let mut seen = HashSet::new();
+    for (name, address) in addresses {
+        if address.is_zero() {
+            return Err(NetworkError::ZeroAddress(name));
+        }
+        if !seen.insert(address) {
+            return Err(NetworkError::DuplicateAddress(address));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::host_contracts;
+    use alloy::primitives::U256;
+
+    const TOKEN: Address = Address::repeat_byte(0x70);
+    const PREDEPLOY: Address = Address::repeat_byte(0x71);
+
+    fn constants() -> NetworkConstants {
+        NetworkConstants {
+            host_chain_id: 1,
+            rollup_chain_id: 17,
+            deploy_block_number: 100,
+            host: host_contracts(),
+            rollup: RollupContracts {
+                passage: Address::repeat_byte(5),
+                orders: Address::repeat_byte(6),
+            },
+            token_predeploys: BTreeMap::new(),
+            signing_domain: DOMAIN_BINDING.to_owned(),
+        }
+    }
+
+    #[test]
+    fn json() {
+        let mut constants = constants();
+        constants.token_predeploys.insert(TOKEN, PREDEPLOY);
+        let json = serde_json::to_string(&constants).unwrap();
+        assert_eq!(NetworkConstants::from_json(&json).unwrap(), constants);
+
+        // the signing domain and predeploys are optional
+        let mut value = serde_json::to_value(&constants).unwrap();
+        value.as_object_mut().unwrap().remove("signingDomain");
+        value.as_object_mut().unwrap().remove("tokenPredeploys");
+        let loaded = NetworkConstants::from_json(&value.to_string()).unwrap();
+        assert_eq!(loaded.signing_domain, DOMAIN_BINDING);
+        assert!(loaded.token_predeploys.is_empty());
+
+        assert!(matches!(NetworkConstants::from_json("{}"), Err(NetworkError::Json(_))));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml() {
+        let mut constants = constants();
+        constants.token_predeploys.insert(TOKEN, PREDEPLOY);
+        let toml = toml::to_string(&constants).unwrap();
+        assert_eq!(NetworkConstants::from_toml(&toml).unwrap(), constants);
+    }
+
+    #[test]
+    fn validation() {
+        let base = constants();
+
+        let mut c = base.clone();
+        c.rollup_chain_id = c.host_chain_id;
+        assert!(matches!(c.validate(), Err(NetworkError::SameChainId(1))));
+
+        let mut c = base.clone();
+        c.host_chain_id = 0;
+        assert!(matches!(c.validate(), Err(NetworkError::ZeroChainId)));
+
+        let mut c = base.clone();
+        c.host.orders = c.host.passage;
+        assert!(
+            matches!(c.validate(), Err(NetworkError::DuplicateAddress(a)) if a == c.host.passage)
+        );
+
+        let mut c = base.clone();
+        c.token_predeploys.insert(TOKEN, c.rollup.orders);
+        assert!(matches!(c.validate(), Err(NetworkError::DuplicateAddress(_))));
+
+        let mut c = base.clone();
+        c.rollup.passage = Address::ZERO;
+        assert!(matches!(c.validate(), Err(NetworkError::ZeroAddress("rollup passage"))));
+
+        // the same address may be used on both chains
+        let mut c = base;
+        c.rollup.orders = c.host.orders;
+        c.validate().unwrap();
+    }
+
+    #[test]
+    fn helpers() {
+        let mut constants = constants();
+        constants.token_predeploys.insert(TOKEN, PREDEPLOY);
+
+        let enter = Passage::EnterToken {
+            rollupChainId: U256::from(constants.rollup_chain_id),
+            token: TOKEN,
+            rollupRecipient: Address::repeat_byte(1),
+            amount: U256::from(5),
+        };
+        let mint = constants.mint_for_enter_token(&enter, B256::ZERO, 0).unwrap();
+        assert_eq!(mint.to, PREDEPLOY);
+
+        let policy = constants.tx_policy([TxType::Eip1559]);
+        assert_eq!(policy.chain_ids, [constants.rollup_chain_id]);
+
+        let unknown = Passage::EnterToken { token: Address::repeat_byte(2), ..enter };
+        assert!(constants.mint_for_enter_token(&unknown, B256::ZERO, 0).is_none());
+
+        let request = SignRequest {
+            host_block_number: U256::ZERO,
+            host_chain_id: U256::from(constants.host_chain_id),
+            ru_chain_id: U256::from(constants.rollup_chain_id),
+            gas_limit: U256::ZERO,
+            ru_reward_address: Address::ZERO,
+            contents: B256::ZERO,
+        };
+        assert_eq!(constants.signing_hash(&request), request.signing_hash());
+        constants.signing_domain = "other".to_owned();
+        assert_ne!(constants.signing_hash(&request), request.signing_hash());
+    }
+}
@@ -48,6 +48,12 @@ impl AggregateOrders {
         order.inputs.iter().for_each(|i| self.ingest_input(i));
     }
 
+    /// Ingest a set of fill outputs into the aggregate orders. Fills
+    /// transfer assets to recipients, so only the outputs are affected.
+    pub fn ingest_fill<'a>(&mut self, outputs: impl IntoIterator<Item = &'a RollupOrders::Output>) {
+        outputs.into_iter().for_each(|o| self.ingest_output(o));
+    }
+
     /// Extend the orders with a new set of orders.
     pub fn extend<'a>(&mut self, orders: impl IntoIterator<Item = &'a RollupOrders::Order>) {
         for order in orders {
@@ -1,8 +1,8 @@
 use alloy::primitives::{Address, Keccak256, B256, U256};
 use serde::{Deserialize, Serialize};
 
-/// The domain binding for the signing service.
-const DOMAIN_BINDING: &str = "init4.sequencer.v0";
+/// The default domain binding for the signing service.
+pub const DOMAIN_BINDING: &str = "init4.sequencer.v0";
 
 /// A request to sign a rollup block.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -25,8 +25,14 @@ pub struct SignRequest {
 impl SignRequest {
     /// Compute the signing hash for this sig request
     pub fn signing_hash(&self) -> B256 {
+        self.signing_hash_with_domain(DOMAIN_BINDING)
+    }
+
+    /// Compute the signing hash for this sig request, with a domain binding
+    /// other than [`DOMAIN_BINDING`].
+    pub fn signing_hash_with_domain(&self, domain: &str) -> B256 {
         let mut hasher = Keccak256::new();
-        hasher.update(DOMAIN_BINDING);
+        hasher.update(domain);
         hasher.update(self.host_chain_id.to_be_bytes::<32>());
         hasher.update(self.ru_chain_id.to_be_bytes::<32>());
         hasher.update(self.host_block_number.to_be_bytes::<32>());
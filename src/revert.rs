@@ -0,0 +1,272 @@
+use crate::{Passage, RollupOrders, Transactor, Zenith};
+use alloy::contract::Error as ContractError;
+use alloy::primitives::Bytes;
+use alloy::sol_types::{Panic, Revert, SolError, SolInterface};
+use alloy::transports::TransportError;
+use std::fmt;
+
+/// How a call that reverted may be retried. See [`ZenithRevert::retry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Retry {
+    /// The same call may succeed in a later host block.
+    Unchanged,
+    /// The call commits to the host block it targets, via the signed
+    /// `hostBlockNumber` of the block header. It must be re-signed for a
+    /// later host block, and may then succeed.
+    NextHostBlock,
+    /// The call must change, or the revert cannot be classified.
+    Never,
+}
+
+/// A decoded revert of a call to any of the Zenith contracts.
+///
+/// Several contracts share errors, for example the OpenZeppelin errors
+/// `InsufficientBalance` and `SafeERC20FailedOperation`. The revert data does
+/// not identify the contract, so the error sets are tried in the order
+/// [`Zenith`], [`Transactor`], [`Passage`], [`RollupOrders`], and a shared
+/// error is attributed to the first contract that defines it. In particular,
+/// every `RollupPassage` error is also a [`Passage`] error, and `HostOrders`
+/// and [`RollupOrders`] share an error set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZenithRevert {
+    /// An error of the [`Zenith`] contract.
+    Zenith(Zenith::ZenithErrors),
+    /// An error of the [`Transactor`] contract.
+    Transactor(Transactor::TransactorErrors),
+    /// An error of the [`Passage`] or `RollupPassage` contracts.
+    Passage(Passage::PassageErrors),
+    /// An error of the [`RollupOrders`] or `HostOrders` contracts.
+    Orders(RollupOrders::RollupOrdersErrors),
+    /// A `require` or `revert` with a reason string.
+    Revert(Revert),
+    /// A Solidity panic, such as an arithmetic overflow.
+    Panic(Panic),
+    /// Revert data that matches no known error, including empty data.
+    Unknown(Bytes),
+}
+
+impl ZenithRevert {
+    /// Decode revert data. Data that matches no known error is returned as
+    /// [`ZenithRevert::Unknown`].
+    pub fn decode(data: &[u8]) -> Self {
+        if let Ok(e) = Zenith::ZenithErrors::abi_decode(data, true) {
+            Self::Zenith(e)
+        } else if let Ok(e) = Transactor::TransactorErrors::abi_decode(data, true) {
+            Self::Transactor(e)
+        } else if let Ok(e) = Passage::PassageErrors::abi_decode(data, true) {
+            Self::Passage(e)
+        } else if let Ok(e) = RollupOrders::RollupOrdersErrors::abi_decode(data, true) {
+            Self::Orders(e)
+        } else if let Ok(e) = Revert::abi_decode(data, true) {
+            Self::Revert(e)
+        } else if let Ok(e) = Panic::abi_decode(data, true) {
+            Self::Panic(e)
+        } else {
+            Self::Unknown(Bytes::copy_from_slice(data))
+        }
+    }
+
+    /// Decode the revert in an RPC error, such as the response to an
+    /// `eth_call` or `eth_estimateGas`. Returns `None` if the error is not a
+    /// revert.
+    pub fn from_transport_error(error: &TransportError) -> Option<Self> {
+        error.as_error_resp()?.as_revert_data().map(|data| Self::decode(&data))
+    }
+
+    /// Decode the revert in an error returned by a contract instance.
+    /// Returns `None` if the error is not a revert.
+    pub fn from_contract_error(error: &ContractError) -> Option<Self> {
+        match error {
+            ContractError::TransportError(error) => Self::from_transport_error(error),
+            _ => None,
+        }
+    }
+
+    /// How the call may be retried.
+    ///
+    /// [`Zenith::IncorrectHostBlock`] and
+    /// [`Zenith::OneRollupBlockPerHostBlock`] are resolved by re-signing the
+    /// block header for a later host block, see [`Retry::NextHostBlock`].
+    /// [`Transactor::PerBlockTransactGasLimit`] resets every block, so the
+    /// same call may be retried, see [`Retry::Unchanged`]. All other errors
+    /// require the call to change, or cannot be classified.
+    pub const fn retry(&self) -> Retry {
+        match self {
+            Self::Zenith(
+                Zenith::ZenithErrors::IncorrectHostBlock(_)
+                | Zenith::ZenithErrors::OneRollupBlockPerHostBlock(_),
+            ) => Retry::NextHostBlock,
+            Self::Transactor(Transactor::TransactorErrors::PerBlockTransactGasLimit(_)) => {
+                Retry::Unchanged
+            }
+            _ => Retry::Never,
+        }
+    }
+
+    /// True if the call may succeed in a later host block, possibly after
+    /// re-signing. See [`ZenithRevert::retry`].
+    pub const fn is_retryable(&self) -> bool {
+        !matches!(self.retry(), Retry::Never)
+    }
+
+    /// A human-readable explanation of the revert.
+    pub fn explanation(&self) -> String {
+        use Passage::PassageErrors as P;
+        use RollupOrders::RollupOrdersErrors as O;
+        use Transactor::TransactorErrors as T;
+        use Zenith::ZenithErrors as Z;
+
+        match self {
+            Self::Zenith(Z::BadSignature(e)) => format!(
+                "the block signature recovers to {}, which is not a permissioned sequencer",
+                e.derivedSequencer
+            ),
+            Self::Zenith(Z::IncorrectHostBlock(_)) => {
+                "the block header targets a different host block than the one it was included in"
+                    .to_owned()
+            }
+            Self::Zenith(Z::OneRollupBlockPerHostBlock(_)) => {
+                "a rollup block was already submitted in this host block".to_owned()
+            }
+            Self::Zenith(Z::OnlySequencerAdmin(_)) => {
+                "the caller is not the sequencer admin".to_owned()
+            }
+            Self::Transactor(T::OnlyGasAdmin(_)) => "the caller is not the gas admin".to_owned(),
+            Self::Transactor(T::PerBlockTransactGasLimit(_)) => {
+                "the transact gas limit of this host block is exhausted".to_owned()
+            }
+            Self::Transactor(T::PerTransactGasLimit(_)) => {
+                "the transact gas limit exceeds the per-transact limit".to_owned()
+            }
+            Self::Passage(P::DisallowedEnter(e)) => {
+                format!("entering the rollup with token {} is not allowed", e.token)
+            }
+            Self::Passage(P::OnlyTokenAdmin(_)) => "the caller is not the token admin".to_owned(),
+            Self::Passage(P::AddressEmptyCode(e)) => {
+                format!("the called address {} has no code", e.target)
+            }
+            Self::Passage(P::FailedCall(_)) => "a call made by the contract failed".to_owned(),
+            Self::Passage(P::InsufficientBalance(e)) => {
+                format!("insufficient balance: have {}, need {}", e.balance, e.needed)
+            }
+            Self::Passage(P::ReentrancyGuardReentrantCall(_)) => {
+                "the contract was reentered".to_owned()
+            }
+            Self::Passage(P::SafeERC20FailedOperation(e)) => {
+                format!("a transfer of token {} failed", e.token)
+            }
+            Self::Orders(O::LengthMismatch(_)) => {
+                "the order inputs and outputs have different lengths".to_owned()
+            }
+            Self::Orders(O::OrderExpired(_)) => "the order deadline has passed".to_owned(),
+            Self::Orders(O::OutputMismatch(_)) => {
+                "the permit2 witness does not match the order outputs".to_owned()
+            }
+            Self::Orders(O::AddressEmptyCode(e)) => {
+                format!("the called address {} has no code", e.target)
+            }
+            Self::Orders(O::FailedCall(_)) => "a call made by the contract failed".to_owned(),
+            Self::Orders(O::InsufficientBalance(e)) => {
+                format!("insufficient balance: have {}, need {}", e.balance, e.needed)
+            }
+            Self::Orders(O::ReentrancyGuardReentrantCall(_)) => {
+                "the contract was reentered".to_owned()
+            }
+            Self::Orders(O::SafeERC20FailedOperation(e)) => {
+                format!("a transfer of token {} failed", e.token)
+            }
+            Self::Revert(e) => e.to_string(),
+            Self::Panic(e) => e.to_string(),
+            Self::Unknown(data) if data.is_empty() => "reverted without data".to_owned(),
+            Self::Unknown(data) => format!("unknown revert data {data}"),
+        }
+    }
+}
+
+impl fmt::Display for ZenithRevert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.explanation())
+    }
+}
+
+impl std::error::Error for ZenithRevert {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy::primitives::{Address, U256};
+    use alloy::rpc::json_rpc::ErrorPayload;
+    use alloy::transports::RpcError;
+
+    #[test]
+    fn decode() {
+        let data = Zenith::IncorrectHostBlock {}.abi_encode();
+        let revert = ZenithRevert::decode(&data);
+        assert_eq!(
+            revert,
+            ZenithRevert::Zenith(Zenith::ZenithErrors::IncorrectHostBlock(
+                Zenith::IncorrectHostBlock {}
+            ))
+        );
+        assert_eq!(revert.retry(), Retry::NextHostBlock);
+        assert!(revert.is_retryable());
+
+        let data = Zenith::BadSignature { derivedSequencer: Address::repeat_byte(1) }.abi_encode();
+        let revert = ZenithRevert::decode(&data);
+        assert!(!revert.is_retryable());
+        assert!(revert.explanation().contains(&Address::repeat_byte(1).to_string()));
+
+        let data = Transactor::PerTransactGasLimit {}.abi_encode();
+        assert!(matches!(
+            ZenithRevert::decode(&data),
+            ZenithRevert::Transactor(Transactor::TransactorErrors::PerTransactGasLimit(_))
+        ));
+
+        let data = RollupOrders::OrderExpired {}.abi_encode();
+        assert!(matches!(
+            ZenithRevert::decode(&data),
+            ZenithRevert::Orders(RollupOrders::RollupOrdersErrors::OrderExpired(_))
+        ));
+
+        // shared errors are attributed to the first contract defining them
+        let data =
+            RollupOrders::InsufficientBalance { balance: U256::from(1), needed: U256::from(2) }
+                .abi_encode();
+        let revert = ZenithRevert::decode(&data);
+        assert!(matches!(revert, ZenithRevert::Passage(_)));
+        assert_eq!(revert.to_string(), "insufficient balance: have 1, need 2");
+    }
+
+    #[test]
+    fn fallbacks() {
+        let data = Revert::from("nope").abi_encode();
+        assert_eq!(ZenithRevert::decode(&data).to_string(), "revert: nope");
+
+        let data = Panic::from(0x11).abi_encode();
+        assert!(matches!(ZenithRevert::decode(&data), ZenithRevert::Panic(_)));
+
+        let revert = ZenithRevert::decode(&[0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(revert, ZenithRevert::Unknown(Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef])));
+        assert_eq!(revert.retry(), Retry::Never);
+        assert_eq!(ZenithRevert::decode(&[]).to_string(), "reverted without data");
+    }
+
+    #[test]
+    fn transport_error() {
+        let data: Bytes = Transactor::PerBlockTransactGasLimit {}.abi_encode().into();
+        let payload: ErrorPayload = serde_json::from_value(serde_json::json!({
+            "code": 3,
+            "message": "execution reverted",
+            "data": data,
+        }))
+        .unwrap();
+        let error: TransportError = RpcError::ErrorResp(payload);
+
+        let revert = ZenithRevert::from_transport_error(&error).unwrap();
+        assert_eq!(revert.retry(), Retry::Unchanged);
+        assert!(ZenithRevert::from_contract_error(&error.into()).is_some());
+
+        let error: TransportError = RpcError::NullResp;
+        assert!(ZenithRevert::from_transport_error(&error).is_none());
+    }
+}
@@ -0,0 +1,451 @@
+use crate::{
+    Coder, PolicyViolation, RecoverSigner, TxDecodeError, TxMint, TxTransact, ZenithTransaction,
+    MINTER_ADDRESS, MINT_TX_TYPE, TRANSACT_TX_TYPE,
+};
+use alloy::consensus::{Transaction, TxType};
+use alloy::eips::eip2718::{Decodable2718, Eip2718Error, Eip2718Result, Encodable2718, Typed2718};
+use alloy::eips::eip2930::AccessList;
+use alloy::eips::eip7702::SignedAuthorization;
+use alloy::primitives::{Address, Bytes, ChainId, SignatureError, TxKind, B256, U256};
+use alloy::rlp::{BufMut, Decodable, Encodable};
+use serde::{Deserialize, Serialize};
+
+/// A rollup transaction: either an Ethereum transaction, or a system
+/// transaction caused by an event on the host chain.
+///
+/// System transactions are encoded as their EIP-2718 type byte followed by
+/// the RLP list of their fields. See [`MINT_TX_TYPE`] and
+/// [`TRANSACT_TX_TYPE`].
+///
+/// Like [`ZenithTransaction`], system transactions are serialized with a
+/// `type` field holding their type byte, `"0x7c"` or `"0x7d"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "serde_from::MaybeTaggedRollupTx", from = "serde_from::MaybeTaggedRollupTx")]
+#[allow(clippy::large_enum_variant)]
+pub enum RollupTxEnvelope {
+    /// An Ethereum transaction.
+    Ethereum(ZenithTransaction),
+    /// A mint from [`MINTER_ADDRESS`].
+    Mint(TxMint),
+    /// A call forced by the host chain.
+    Transact(TxTransact),
+}
+
+mod serde_from {
+    use super::*;
+
+    /// A system transaction, tagged with its type byte.
+    #[derive(Serialize, Deserialize)]
+    #[serde(tag = "type")]
+    pub(super) enum TaggedSystemTx {
+        #[serde(rename = "0x7c")]
+        Mint(TxMint),
+        #[serde(rename = "0x7d")]
+        Transact(TxTransact),
+    }
+
+    /// The serde form of [`RollupTxEnvelope`]. Ethereum transactions are
+    /// tagged by their own serde impl.
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    #[allow(clippy::large_enum_variant)]
+    pub(super) enum MaybeTaggedRollupTx {
+        System(TaggedSystemTx),
+        Ethereum(ZenithTransaction),
+    }
+
+    impl From<MaybeTaggedRollupTx> for RollupTxEnvelope {
+        fn from(tx: MaybeTaggedRollupTx) -> Self {
+            match tx {
+                MaybeTaggedRollupTx::System(TaggedSystemTx::Mint(tx)) => Self::Mint(tx),
+                MaybeTaggedRollupTx::System(TaggedSystemTx::Transact(tx)) => Self::Transact(tx),
+                MaybeTaggedRollupTx::Ethereum(tx) => Self::Ethereum(tx),
+            }
+        }
+    }
+
+    impl From<RollupTxEnvelope> for MaybeTaggedRollupTx {
+        fn from(tx: RollupTxEnvelope) -> Self {
+            match tx {
+                RollupTxEnvelope::Ethereum(tx) => Self::Ethereum(tx),
+                RollupTxEnvelope::Mint(tx) => Self::System(TaggedSystemTx::Mint(tx)),
+                RollupTxEnvelope::Transact(tx) => Self::System(TaggedSystemTx::Transact(tx)),
+            }
+        }
+    }
+}
+
+impl From<ZenithTransaction> for RollupTxEnvelope {
+    fn from(tx: ZenithTransaction) -> Self {
+        Self::Ethereum(tx)
+    }
+}
+
+impl From<TxMint> for RollupTxEnvelope {
+    fn from(tx: TxMint) -> Self {
+        Self::Mint(tx)
+    }
+}
+
+impl From<TxTransact> for RollupTxEnvelope {
+    fn from(tx: TxTransact) -> Self {
+        Self::Transact(tx)
+    }
+}
+
+impl RollupTxEnvelope {
+    /// True if the transaction is a system transaction.
+    pub const fn is_system(&self) -> bool {
+        !matches!(self, Self::Ethereum(_))
+    }
+
+    /// Get the Ethereum transaction, if any.
+    pub const fn as_ethereum(&self) -> Option<&ZenithTransaction> {
+        match self {
+            Self::Ethereum(tx) => Some(tx),
+            _ => None,
+        }
+    }
+
+    /// The hash of the transaction: the keccak256 hash of its EIP-2718
+    /// encoding.
+    pub fn tx_hash(&self) -> B256 {
+        match self {
+            Self::Ethereum(tx) => *tx.tx_hash(),
+            Self::Mint(tx) => tx.tx_hash(),
+            Self::Transact(tx) => tx.tx_hash(),
+        }
+    }
+
+    fn inner(&self) -> &dyn Transaction {
+        match self {
+            Self::Ethereum(tx) => tx,
+            Self::Mint(tx) => tx,
+            Self::Transact(tx) => tx,
+        }
+    }
+}
+
+impl RecoverSigner for RollupTxEnvelope {
+    /// Recover the signer of an Ethereum transaction. The sender of a system
+    /// transaction is given by the transaction itself.
+    fn recover_signer(&self) -> Result<Address, SignatureError> {
+        match self {
+            Self::Ethereum(tx) => tx.recover_signer(),
+            Self::Mint(_) => Ok(MINTER_ADDRESS),
+            Self::Transact(tx) => Ok(tx.from),
+        }
+    }
+}
+
+impl Typed2718 for RollupTxEnvelope {
+    fn ty(&self) -> u8 {
+        match self {
+            Self::Ethereum(tx) => tx.ty(),
+            Self::Mint(_) => MINT_TX_TYPE,
+            Self::Transact(_) => TRANSACT_TX_TYPE,
+        }
+    }
+}
+
+impl Encodable2718 for RollupTxEnvelope {
+    fn encode_2718_len(&self) -> usize {
+        match self {
+            Self::Ethereum(tx) => tx.encode_2718_len(),
+            Self::Mint(tx) => 1 + tx.length(),
+            Self::Transact(tx) => 1 + tx.length(),
+        }
+    }
+
+    fn encode_2718(&self, out: &mut dyn BufMut) {
+        match self {
+            Self::Ethereum(tx) => tx.encode_2718(out),
+            Self::Mint(tx) => {
+                out.put_u8(MINT_TX_TYPE);
+                tx.encode(out);
+            }
+            Self::Transact(tx) => {
+                out.put_u8(TRANSACT_TX_TYPE);
+                tx.encode(out);
+            }
+        }
+    }
+}
+
+impl Decodable2718 for RollupTxEnvelope {
+    fn typed_decode(ty: u8, buf: &mut &[u8]) -> Eip2718Result<Self> {
+        match ty {
+            MINT_TX_TYPE => Ok(Self::Mint(TxMint::decode(buf)?)),
+            TRANSACT_TX_TYPE => Ok(Self::Transact(TxTransact::decode(buf)?)),
+            _ => ZenithTransaction::typed_decode(ty, buf).map(Self::Ethereum),
+        }
+    }
+
+    fn fallback_decode(buf: &mut &[u8]) -> Eip2718Result<Self> {
+        ZenithTransaction::fallback_decode(buf).map(Self::Ethereum)
+    }
+}
+
+impl Transaction for RollupTxEnvelope {
+    fn chain_id(&self) -> Option<ChainId> {
+        self.inner().chain_id()
+    }
+
+    fn nonce(&self) -> u64 {
+        self.inner().nonce()
+    }
+
+    fn gas_limit(&self) -> u64 {
+        self.inner().gas_limit()
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        self.inner().gas_price()
+    }
+
+    fn max_fee_per_gas(&self) -> u128 {
+        self.inner().max_fee_per_gas()
+    }
+
+    fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        self.inner().max_priority_fee_per_gas()
+    }
+
+    fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        self.inner().max_fee_per_blob_gas()
+    }
+
+    fn priority_fee_or_price(&self) -> u128 {
+        self.inner().priority_fee_or_price()
+    }
+
+    fn effective_gas_price(&self, base_fee: Option<u64>) -> u128 {
+        self.inner().effective_gas_price(base_fee)
+    }
+
+    fn is_dynamic_fee(&self) -> bool {
+        self.inner().is_dynamic_fee()
+    }
+
+    fn kind(&self) -> TxKind {
+        self.inner().kind()
+    }
+
+    fn is_create(&self) -> bool {
+        self.inner().is_create()
+    }
+
+    fn value(&self) -> U256 {
+        self.inner().value()
+    }
+
+    fn input(&self) -> &Bytes {
+        self.inner().input()
+    }
+
+    fn access_list(&self) -> Option<&AccessList> {
+        self.inner().access_list()
+    }
+
+    fn blob_versioned_hashes(&self) -> Option<&[B256]> {
+        self.inner().blob_versioned_hashes()
+    }
+
+    fn authorization_list(&self) -> Option<&[SignedAuthorization]> {
+        self.inner().authorization_list()
+    }
+}
+
+/// Coder for [`RollupTxEnvelope`].
+///
+/// System transactions are derived from host chain events, and are never
+/// valid in block data. The coder does not decode them, so they are dropped
+/// by [`decode_txns`], reported as [`TxDecodeError::Policy`] by
+/// [`decode_txns_strict`], and refused by [`ZenithBlockBuilder`]. They are
+/// still encoded by [`ZenithBlock::transactions_root`].
+///
+/// [`decode_txns`]: crate::decode_txns
+/// [`decode_txns_strict`]: crate::decode_txns_strict
+/// [`ZenithBlockBuilder`]: crate::ZenithBlockBuilder
+///
+/// [`ZenithBlock::transactions_root`]: crate::ZenithBlock::transactions_root
+#[derive(Debug, Clone, Copy)]
+pub struct RollupTxCoder;
+
+impl Coder for RollupTxCoder {
+    type Tx = RollupTxEnvelope;
+
+    fn encode(t: &RollupTxEnvelope) -> Vec<u8> {
+        t.encoded_2718()
+    }
+
+    fn encode_into(t: &RollupTxEnvelope, out: &mut Vec<u8>) {
+        t.encode_2718(out)
+    }
+
+    fn decode(buf: &mut &[u8]) -> Option<RollupTxEnvelope>
+    where
+        Self: Sized,
+    {
+        RollupTxEnvelope::decode_2718(buf).ok().filter(|tx| !tx.is_system())
+    }
+
+    fn decode_with<E>(
+        buf: &mut &[u8],
+        check: impl FnOnce(&RollupTxEnvelope) -> Result<(), E>,
+    ) -> Result<Option<RollupTxEnvelope>, E>
+    where
+        Self: Sized,
+    {
+        let Ok(tx) = RollupTxEnvelope::decode_2718(buf) else {
+            return Ok(None);
+        };
+        check(&tx)?;
+        Ok(Some(tx).filter(|tx| !tx.is_system()))
+    }
+
+    fn try_decode(buf: &mut &[u8]) -> Result<RollupTxEnvelope, TxDecodeError>
+    where
+        Self: Sized,
+    {
+        if let Some(&ty) = buf.first() {
+            if ty < 0x80
+                && ty != MINT_TX_TYPE
+                && ty != TRANSACT_TX_TYPE
+                && TxType::try_from(ty).is_err()
+            {
+                return Err(TxDecodeError::UnsupportedType(ty));
+            }
+        }
+        let tx = RollupTxEnvelope::decode_2718(buf).map_err(|e| match e {
+            Eip2718Error::UnexpectedType(ty) => TxDecodeError::UnsupportedType(ty),
+            Eip2718Error::RlpError(e) => TxDecodeError::Inner2718(e),
+            _ => TxDecodeError::Rejected,
+        })?;
+        if tx.is_system() {
+            return Err(TxDecodeError::Policy(PolicyViolation::TxType(tx.ty())));
+        }
+        Ok(tx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{header, tx, TEST_CHAIN_ID};
+    use crate::{
+        decode_txns, decode_txns_strict, encode_txns, BlockLimitError, ZenithBlock,
+        ZenithBlockBuilder,
+    };
+    use alloy::primitives::keccak256;
+
+    fn mint() -> TxMint {
+        TxMint {
+            chain_id: TEST_CHAIN_ID,
+            host_tx_hash: B256::repeat_byte(1),
+            log_index: 3,
+            to: Address::repeat_byte(2),
+            value: U256::from(100),
+            gas_limit: 0,
+            input: Bytes::new(),
+        }
+    }
+
+    fn transact() -> TxTransact {
+        TxTransact {
+            chain_id: TEST_CHAIN_ID,
+            host_tx_hash: B256::repeat_byte(1),
+            log_index: 4,
+            from: Address::repeat_byte(3),
+            to: Address::repeat_byte(4),
+            value: U256::ZERO,
+            gas_limit: 100_000,
+            max_fee_per_gas: 10,
+            input: Bytes::from_static(&[1, 2, 3]),
+        }
+    }
+
+    #[test]
+    fn roundtrip_2718() {
+        let txs: Vec<RollupTxEnvelope> = vec![tx(0, 10).into(), mint().into(), transact().into()];
+        for tx in &txs {
+            let encoded = tx.encoded_2718();
+            assert_eq!(encoded.len(), tx.encode_2718_len());
+            assert_eq!(encoded[0], tx.ty());
+            assert_eq!(&RollupTxEnvelope::decode_2718(&mut encoded.as_slice()).unwrap(), tx);
+            assert_eq!(tx.tx_hash(), keccak256(&encoded));
+        }
+        assert_eq!(txs[0].tx_hash(), *tx(0, 10).tx_hash());
+
+        // the hash commits to the host log
+        let mut other = mint();
+        other.log_index += 1;
+        assert_ne!(RollupTxEnvelope::from(other).tx_hash(), txs[1].tx_hash());
+
+        assert_eq!(
+            RollupTxCoder::try_decode(&mut [0x7b, 0xc0].as_slice()),
+            Err(TxDecodeError::UnsupportedType(0x7b))
+        );
+    }
+
+    #[test]
+    fn transaction() {
+        let mint = RollupTxEnvelope::from(mint());
+        assert_eq!(mint.recover_signer().unwrap(), MINTER_ADDRESS);
+        assert_eq!(mint.chain_id(), Some(TEST_CHAIN_ID));
+        assert_eq!(mint.effective_gas_price(Some(7)), 0);
+
+        let transact = RollupTxEnvelope::from(transact());
+        assert_eq!(transact.recover_signer().unwrap(), Address::repeat_byte(3));
+        assert_eq!(transact.effective_gas_price(Some(7)), 7);
+        assert_eq!(transact.effective_gas_price(Some(20)), 10);
+    }
+
+    #[test]
+    fn block_data() {
+        let txs: Vec<RollupTxEnvelope> = vec![tx(0, 10).into(), mint().into(), tx(1, 10).into()];
+        let data = encode_txns::<RollupTxCoder>(&txs);
+        assert_eq!(
+            decode_txns::<RollupTxCoder>(&data),
+            vec![RollupTxEnvelope::from(tx(0, 10)), tx(1, 10).into()]
+        );
+        let report = decode_txns_strict::<RollupTxCoder>(&data);
+        assert_eq!(report.failures[0].index, 1);
+        assert_eq!(
+            report.failures[0].error,
+            TxDecodeError::Policy(PolicyViolation::TxType(MINT_TX_TYPE))
+        );
+
+        // the builder refuses system transactions
+        let builder = ZenithBlockBuilder::<RollupTxCoder>::new(header());
+        assert_eq!(
+            builder.check(&txs[1]),
+            Err(BlockLimitError::Policy(PolicyViolation::TxType(MINT_TX_TYPE)))
+        );
+        builder.check(&txs[0]).unwrap();
+
+        let report =
+            decode_txns_strict::<RollupTxCoder>(&encode_txns::<crate::Alloy2718Coder>(&[tx(
+                0, 10,
+            )]));
+        assert_eq!(report.transactions, vec![RollupTxEnvelope::from(tx(0, 10))]);
+
+        // system transactions are part of the transactions root
+        let block: ZenithBlock<RollupTxCoder> = ZenithBlock::new(header(), txs.clone());
+        let proof = block.transaction_proof(1).unwrap();
+        proof.verify::<RollupTxCoder>(block.transactions_root(), &txs[1]).unwrap();
+    }
+
+    #[test]
+    fn serde() {
+        for tx in [RollupTxEnvelope::from(tx(0, 10)), mint().into(), transact().into()] {
+            let json = serde_json::to_value(&tx).unwrap();
+            assert_eq!(json["type"], format!("{:#x}", tx.ty()));
+            assert_eq!(serde_json::from_value::<RollupTxEnvelope>(json).unwrap(), tx);
+        }
+
+        // system transactions require their tag
+        let json = serde_json::to_value(mint()).unwrap();
+        assert!(serde_json::from_value::<RollupTxEnvelope>(json).is_err());
+    }
+}
@@ -0,0 +1,281 @@
+use crate::{mintCall, Passage, MINTER_ADDRESS};
+use alloy::consensus::Transaction;
+use alloy::eips::eip2718::Typed2718;
+use alloy::eips::eip2930::AccessList;
+use alloy::eips::eip7702::SignedAuthorization;
+use alloy::primitives::{keccak256, Address, Bytes, ChainId, TxKind, B256, U256};
+use alloy::rlp::{BufMut, Decodable, Encodable, Header};
+use alloy::sol_types::SolCall;
+use serde::{Deserialize, Serialize};
+
+/// The EIP-2718 type of a [`TxMint`].
+pub const MINT_TX_TYPE: u8 = 0x7c;
+
+/// The gas limit of a [`TxMint`] that calls a token predeploy.
+pub const TOKEN_MINT_GAS_LIMIT: u64 = 100_000;
+
+/// A system transaction that mints value on the rollup, sent by
+/// [`MINTER_ADDRESS`] as the result of an event on the host chain.
+///
+/// Mints are unsigned, pay no fees, and do not consume a nonce: their nonce
+/// is always zero, and the nonce of [`MINTER_ADDRESS`] is never incremented.
+/// They are identified by the host transaction and log that caused them, so
+/// their hashes are deterministic. See [`TxMint::from_enter`] and
+/// [`TxMint::from_enter_token`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TxMint {
+    /// The rollup chain id.
+    #[serde(with = "alloy::serde::quantity")]
+    pub chain_id: ChainId,
+    /// The hash of the host transaction that emitted the log.
+    pub host_tx_hash: B256,
+    /// The index of the log in the host block.
+    #[serde(with = "alloy::serde::quantity")]
+    pub log_index: u64,
+    /// The recipient of the minted value, or the token to call.
+    pub to: Address,
+    /// The native value minted to the recipient.
+    pub value: U256,
+    /// The gas limit of the call.
+    #[serde(with = "alloy::serde::quantity")]
+    pub gas_limit: u64,
+    /// The calldata.
+    pub input: Bytes,
+}
+
+impl TxMint {
+    /// Create the mint caused by a [`Passage::Enter`] event, emitted by the
+    /// host transaction `host_tx_hash` at `log_index` in the host block.
+    ///
+    /// The amount of the native asset is minted to the recipient, as the
+    /// value of a call with no data and no gas.
+    pub const fn from_enter(enter: &Passage::Enter, host_tx_hash: B256, log_index: u64) -> Self {
+        Self {
+            chain_id: enter.rollup_chain_id(),
+            host_tx_hash,
+            log_index,
+            to: enter.recipient(),
+            value: enter.amount(),
+            gas_limit: 0,
+            input: Bytes::new(),
+        }
+    }
+
+    /// Create the mint caused by a [`Passage::EnterToken`] event, emitted by
+    /// the host transaction `host_tx_hash` at `log_index` in the host block.
+    ///
+    /// `predeploy` is the rollup token predeploy for the event's host token.
+    /// The mint calls [`mintCall`] on the predeploy to mint the amount to the
+    /// recipient, with no value and [`TOKEN_MINT_GAS_LIMIT`] gas.
+    pub fn from_enter_token(
+        enter: &Passage::EnterToken,
+        predeploy: Address,
+        host_tx_hash: B256,
+        log_index: u64,
+    ) -> Self {
+        let call = mintCall { to: enter.recipient(), amount: enter.amount() };
+        Self {
+            chain_id: enter.rollup_chain_id(),
+            host_tx_hash,
+            log_index,
+            to: predeploy,
+            value: U256::ZERO,
+            gas_limit: TOKEN_MINT_GAS_LIMIT,
+            input: call.abi_encode().into(),
+        }
+    }
+
+    /// The sender of the transaction, which is always [`MINTER_ADDRESS`].
+    pub const fn from(&self) -> Address {
+        MINTER_ADDRESS
+    }
+
+    /// The hash of the transaction: the keccak256 hash of its EIP-2718
+    /// encoding. Mints caused by the same host log have the same hash.
+    pub fn tx_hash(&self) -> B256 {
+        let mut buf = Vec::with_capacity(1 + self.length());
+        buf.push(MINT_TX_TYPE);
+        self.encode(&mut buf);
+        keccak256(buf)
+    }
+
+    fn fields_len(&self) -> usize {
+        self.chain_id.length()
+            + self.host_tx_hash.length()
+            + self.log_index.length()
+            + self.to.length()
+            + self.value.length()
+            + self.gas_limit.length()
+            + self.input.length()
+    }
+}
+
+impl Encodable for TxMint {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.fields_len() }.encode(out);
+        self.chain_id.encode(out);
+        self.host_tx_hash.encode(out);
+        self.log_index.encode(out);
+        self.to.encode(out);
+        self.value.encode(out);
+        self.gas_limit.encode(out);
+        self.input.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.fields_len();
+        Header { list: true, payload_length }.length() + payload_length
+    }
+}
+
+impl Decodable for TxMint {
+    fn decode(buf: &mut &[u8]) -> alloy::rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy::rlp::Error::UnexpectedString);
+        }
+        let remaining = buf.len();
+        let tx = Self {
+            chain_id: Decodable::decode(buf)?,
+            host_tx_hash: Decodable::decode(buf)?,
+            log_index: Decodable::decode(buf)?,
+            to: Decodable::decode(buf)?,
+            value: Decodable::decode(buf)?,
+            gas_limit: Decodable::decode(buf)?,
+            input: Decodable::decode(buf)?,
+        };
+        if remaining - buf.len() != header.payload_length {
+            return Err(alloy::rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: remaining - buf.len(),
+            });
+        }
+        Ok(tx)
+    }
+}
+
+impl Typed2718 for TxMint {
+    fn ty(&self) -> u8 {
+        MINT_TX_TYPE
+    }
+}
+
+impl Transaction for TxMint {
+    fn chain_id(&self) -> Option<ChainId> {
+        Some(self.chain_id)
+    }
+
+    fn nonce(&self) -> u64 {
+        0
+    }
+
+    fn gas_limit(&self) -> u64 {
+        self.gas_limit
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        None
+    }
+
+    fn max_fee_per_gas(&self) -> u128 {
+        0
+    }
+
+    fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        None
+    }
+
+    fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        None
+    }
+
+    fn priority_fee_or_price(&self) -> u128 {
+        0
+    }
+
+    fn effective_gas_price(&self, _base_fee: Option<u64>) -> u128 {
+        0
+    }
+
+    fn is_dynamic_fee(&self) -> bool {
+        false
+    }
+
+    fn kind(&self) -> TxKind {
+        TxKind::Call(self.to)
+    }
+
+    fn is_create(&self) -> bool {
+        false
+    }
+
+    fn value(&self) -> U256 {
+        self.value
+    }
+
+    fn input(&self) -> &Bytes {
+        &self.input
+    }
+
+    fn access_list(&self) -> Option<&AccessList> {
+        None
+    }
+
+    fn blob_versioned_hashes(&self) -> Option<&[B256]> {
+        None
+    }
+
+    fn authorization_list(&self) -> Option<&[SignedAuthorization]> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::TEST_CHAIN_ID;
+
+    const HOST_TX: B256 = B256::repeat_byte(0xaa);
+    const RECIPIENT: Address = Address::repeat_byte(1);
+    const TOKEN: Address = Address::repeat_byte(2);
+    const PREDEPLOY: Address = Address::repeat_byte(3);
+
+    #[test]
+    fn enter() {
+        let enter = Passage::Enter {
+            rollupChainId: U256::from(TEST_CHAIN_ID),
+            rollupRecipient: RECIPIENT,
+            amount: U256::from(1_000),
+        };
+        let mint = TxMint::from_enter(&enter, HOST_TX, 7);
+
+        assert_eq!(mint.from(), MINTER_ADDRESS);
+        assert_eq!(mint.chain_id, TEST_CHAIN_ID);
+        assert_eq!(mint.to, RECIPIENT);
+        assert_eq!(mint.value, U256::from(1_000));
+        assert_eq!((mint.gas_limit, mint.nonce()), (0, 0));
+        assert!(mint.input.is_empty());
+
+        assert_eq!(mint.tx_hash(), TxMint::from_enter(&enter, HOST_TX, 7).tx_hash());
+        assert_ne!(mint.tx_hash(), TxMint::from_enter(&enter, HOST_TX, 8).tx_hash());
+        assert_ne!(mint.tx_hash(), TxMint::from_enter(&enter, B256::ZERO, 7).tx_hash());
+    }
+
+    #[test]
+    fn enter_token() {
+        let enter = Passage::EnterToken {
+            rollupChainId: U256::from(TEST_CHAIN_ID),
+            token: TOKEN,
+            rollupRecipient: RECIPIENT,
+            amount: U256::from(1_000),
+        };
+        let mint = TxMint::from_enter_token(&enter, PREDEPLOY, HOST_TX, 7);
+
+        assert_eq!(mint.to, PREDEPLOY);
+        assert_eq!(mint.value, U256::ZERO);
+        assert_eq!(mint.gas_limit, TOKEN_MINT_GAS_LIMIT);
+        let call = mintCall::abi_decode(&mint.input, true).unwrap();
+        assert_eq!((call.to, call.amount), (RECIPIENT, U256::from(1_000)));
+    }
+}
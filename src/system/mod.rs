@@ -0,0 +1,8 @@
+mod envelope;
+pub use envelope::{RollupTxCoder, RollupTxEnvelope};
+
+mod mint;
+pub use mint::{TxMint, MINT_TX_TYPE, TOKEN_MINT_GAS_LIMIT};
+
+mod transact;
+pub use transact::{EnterTransactError, TxTransact, TRANSACT_TX_TYPE};
@@ -0,0 +1,384 @@
+use crate::{LogMeta, Passage, Transactor, TxMint};
+use alloy::consensus::Transaction;
+use alloy::eips::eip2718::Typed2718;
+use alloy::eips::eip2930::AccessList;
+use alloy::eips::eip7702::SignedAuthorization;
+use alloy::primitives::{keccak256, Address, Bytes, ChainId, TxKind, B256, U256};
+use alloy::rlp::{BufMut, Decodable, Encodable, Header};
+use serde::{Deserialize, Serialize};
+
+/// The EIP-2718 type of a [`TxTransact`].
+pub const TRANSACT_TX_TYPE: u8 = 0x7d;
+
+/// A system transaction forced into the rollup by a
+/// [`Transactor::Transact`] event on the host chain.
+///
+/// Forced transactions are unsigned and do not consume a nonce. Gas is paid
+/// by the sender at no more than the max fee per gas, with no priority fee.
+/// They are identified by the host transaction and log that caused them, so
+/// their hashes are deterministic. See [`TxTransact::from_transact`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TxTransact {
+    /// The rollup chain id.
+    #[serde(with = "alloy::serde::quantity")]
+    pub chain_id: ChainId,
+    /// The hash of the host transaction that emitted the log.
+    pub host_tx_hash: B256,
+    /// The index of the log in the host block.
+    #[serde(with = "alloy::serde::quantity")]
+    pub log_index: u64,
+    /// The sender of the call.
+    pub from: Address,
+    /// The target of the call.
+    pub to: Address,
+    /// The value sent with the call.
+    pub value: U256,
+    /// The gas limit of the call.
+    #[serde(with = "alloy::serde::quantity")]
+    pub gas_limit: u64,
+    /// The maximum fee per gas.
+    #[serde(with = "alloy::serde::quantity")]
+    pub max_fee_per_gas: u128,
+    /// The calldata.
+    pub input: Bytes,
+}
+
+/// Errors produced when the events passed to
+/// [`TxTransact::from_enter_transact`] are not from one call to
+/// `Transactor.enterTransact`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum EnterTransactError {
+    /// The events were emitted by different host transactions.
+    #[error("enter emitted by host tx {enter}, transact by host tx {transact}")]
+    HostTxMismatch {
+        /// The host transaction that emitted the enter.
+        enter: B256,
+        /// The host transaction that emitted the transact.
+        transact: B256,
+    },
+    /// The events are for different rollups.
+    #[error("enter is for rollup {enter}, transact is for rollup {transact}")]
+    ChainIdMismatch {
+        /// The rollup chain id of the enter.
+        enter: U256,
+        /// The rollup chain id of the transact.
+        transact: U256,
+    },
+    /// The enter log does not precede the transact log.
+    #[error("enter log {enter} does not precede transact log {transact}")]
+    LogOrder {
+        /// The index of the enter log.
+        enter: u64,
+        /// The index of the transact log.
+        transact: u64,
+    },
+}
+
+impl TxTransact {
+    /// Create the call forced by a [`Transactor::Transact`] event, emitted by
+    /// the host transaction `host_tx_hash` at `log_index` in the host block.
+    ///
+    /// The call is sent from the event's sender, which is the host account
+    /// that called the Transactor, to the same address on the rollup. Gas and
+    /// max fee per gas are taken from the event, saturating at their maximum
+    /// values.
+    pub fn from_transact(
+        transact: &Transactor::Transact,
+        host_tx_hash: B256,
+        log_index: u64,
+    ) -> Self {
+        Self {
+            chain_id: transact.rollup_chain_id(),
+            host_tx_hash,
+            log_index,
+            from: transact.sender(),
+            to: transact.to(),
+            value: transact.value(),
+            gas_limit: transact.gas.saturating_to(),
+            max_fee_per_gas: transact.maxFeePerGas.saturating_to(),
+            input: transact.data().clone(),
+        }
+    }
+
+    /// Create the system transactions caused by a call to
+    /// `Transactor.enterTransact`, which emits a [`Passage::Enter`] event for
+    /// the ether sent, followed by a [`Transactor::Transact`] event.
+    ///
+    /// The ether is minted to the `etherRecipient` by the first transaction,
+    /// so it is available before the forced call. The forced call is sent
+    /// from the host caller, not the `etherRecipient`, and its value is that
+    /// of the event.
+    ///
+    /// Fails if the events were not emitted by the same host transaction for
+    /// the same rollup, with the enter before the transact.
+    pub fn from_enter_transact(
+        enter: &Passage::Enter,
+        enter_meta: &LogMeta,
+        transact: &Transactor::Transact,
+        transact_meta: &LogMeta,
+    ) -> Result<(TxMint, Self), EnterTransactError> {
+        if enter_meta.tx_hash != transact_meta.tx_hash {
+            return Err(EnterTransactError::HostTxMismatch {
+                enter: enter_meta.tx_hash,
+                transact: transact_meta.tx_hash,
+            });
+        }
+        if enter.rollupChainId != transact.rollupChainId {
+            return Err(EnterTransactError::ChainIdMismatch {
+                enter: enter.rollupChainId,
+                transact: transact.rollupChainId,
+            });
+        }
+        if enter_meta.log_index >= transact_meta.log_index {
+            return Err(EnterTransactError::LogOrder {
+                enter: enter_meta.log_index,
+                transact: transact_meta.log_index,
+            });
+        }
+        Ok((
+            TxMint::from_enter(enter, enter_meta.tx_hash, enter_meta.log_index),
+            Self::from_transact(transact, transact_meta.tx_hash, transact_meta.log_index),
+        ))
+    }
+
+    /// The hash of the transaction: the keccak256 hash of its EIP-2718
+    /// encoding. Calls forced by the same host log have the same hash.
+    pub fn tx_hash(&self) -> B256 {
+        let mut buf = Vec::with_capacity(1 + self.length());
+        buf.push(TRANSACT_TX_TYPE);
+        self.encode(&mut buf);
+        keccak256(buf)
+    }
+
+    fn fields_len(&self) -> usize {
+        self.chain_id.length()
+            + self.host_tx_hash.length()
+            + self.log_index.length()
+            + self.from.length()
+            + self.to.length()
+            + self.value.length()
+            + self.gas_limit.length()
+            + self.max_fee_per_gas.length()
+            + self.input.length()
+    }
+}
+
+impl Encodable for TxTransact {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.fields_len() }.encode(out);
+        self.chain_id.encode(out);
+        self.host_tx_hash.encode(out);
+        self.log_index.encode(out);
+        self.from.encode(out);
+        self.to.encode(out);
+        self.value.encode(out);
+        self.gas_limit.encode(out);
+        self.max_fee_per_gas.encode(out);
+        self.input.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.fields_len();
+        Header { list: true, payload_length }.length() + payload_length
+    }
+}
+
+impl Decodable for TxTransact {
+    fn decode(buf: &mut &[u8]) -> alloy::rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy::rlp::Error::UnexpectedString);
+        }
+        let remaining = buf.len();
+        let tx = Self {
+            chain_id: Decodable::decode(buf)?,
+            host_tx_hash: Decodable::decode(buf)?,
+            log_index: Decodable::decode(buf)?,
+            from: Decodable::decode(buf)?,
+            to: Decodable::decode(buf)?,
+            value: Decodable::decode(buf)?,
+            gas_limit: Decodable::decode(buf)?,
+            max_fee_per_gas: Decodable::decode(buf)?,
+            input: Decodable::decode(buf)?,
+        };
+        if remaining - buf.len() != header.payload_length {
+            return Err(alloy::rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: remaining - buf.len(),
+            });
+        }
+        Ok(tx)
+    }
+}
+
+impl Typed2718 for TxTransact {
+    fn ty(&self) -> u8 {
+        TRANSACT_TX_TYPE
+    }
+}
+
+impl Transaction for TxTransact {
+    fn chain_id(&self) -> Option<ChainId> {
+        Some(self.chain_id)
+    }
+
+    fn nonce(&self) -> u64 {
+        0
+    }
+
+    fn gas_limit(&self) -> u64 {
+        self.gas_limit
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        None
+    }
+
+    fn max_fee_per_gas(&self) -> u128 {
+        self.max_fee_per_gas
+    }
+
+    fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        Some(0)
+    }
+
+    fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        None
+    }
+
+    fn priority_fee_or_price(&self) -> u128 {
+        0
+    }
+
+    fn effective_gas_price(&self, base_fee: Option<u64>) -> u128 {
+        base_fee
+            .map_or(self.max_fee_per_gas, |base_fee| (base_fee as u128).min(self.max_fee_per_gas))
+    }
+
+    fn is_dynamic_fee(&self) -> bool {
+        true
+    }
+
+    fn kind(&self) -> TxKind {
+        TxKind::Call(self.to)
+    }
+
+    fn is_create(&self) -> bool {
+        false
+    }
+
+    fn value(&self) -> U256 {
+        self.value
+    }
+
+    fn input(&self) -> &Bytes {
+        &self.input
+    }
+
+    fn access_list(&self) -> Option<&AccessList> {
+        None
+    }
+
+    fn blob_versioned_hashes(&self) -> Option<&[B256]> {
+        None
+    }
+
+    fn authorization_list(&self) -> Option<&[SignedAuthorization]> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::TEST_CHAIN_ID;
+
+    const HOST_TX: B256 = B256::repeat_byte(0xaa);
+    const SENDER: Address = Address::repeat_byte(1);
+    const TARGET: Address = Address::repeat_byte(2);
+    const ETHER_RECIPIENT: Address = Address::repeat_byte(3);
+
+    fn event() -> Transactor::Transact {
+        Transactor::Transact {
+            rollupChainId: U256::from(TEST_CHAIN_ID),
+            sender: SENDER,
+            to: TARGET,
+            data: Bytes::from_static(&[0xde, 0xad]),
+            value: U256::from(5),
+            gas: U256::from(200_000),
+            maxFeePerGas: U256::from(1_000_000_000),
+        }
+    }
+
+    #[test]
+    fn transact() {
+        let tx = TxTransact::from_transact(&event(), HOST_TX, 2);
+
+        assert_eq!(tx.chain_id, TEST_CHAIN_ID);
+        assert_eq!((tx.from, tx.to), (SENDER, TARGET));
+        assert_eq!(tx.value, U256::from(5));
+        assert_eq!(tx.gas_limit, 200_000);
+        assert_eq!(tx.max_fee_per_gas, 1_000_000_000);
+        assert_eq!(tx.input, event().data);
+
+        assert_eq!(tx.tx_hash(), TxTransact::from_transact(&event(), HOST_TX, 2).tx_hash());
+        assert_ne!(tx.tx_hash(), TxTransact::from_transact(&event(), HOST_TX, 3).tx_hash());
+
+        let mut huge = event();
+        huge.gas = U256::MAX;
+        assert_eq!(TxTransact::from_transact(&huge, HOST_TX, 2).gas_limit, u64::MAX);
+    }
+
+    #[test]
+    fn enter_transact() {
+        let enter = Passage::Enter {
+            rollupChainId: U256::from(TEST_CHAIN_ID),
+            rollupRecipient: ETHER_RECIPIENT,
+            amount: U256::from(100),
+        };
+        let meta = |tx_hash, log_index| LogMeta {
+            block_number: 1,
+            block_hash: B256::ZERO,
+            tx_hash,
+            tx_index: 0,
+            log_index,
+        };
+        let (mint, tx) =
+            TxTransact::from_enter_transact(&enter, &meta(HOST_TX, 1), &event(), &meta(HOST_TX, 2))
+                .unwrap();
+
+        assert_eq!((mint.to, mint.value), (ETHER_RECIPIENT, U256::from(100)));
+        assert_eq!(mint.log_index, 1);
+        assert_eq!(tx.from, SENDER);
+        assert_eq!(tx, TxTransact::from_transact(&event(), HOST_TX, 2));
+
+        assert_eq!(
+            TxTransact::from_enter_transact(
+                &enter,
+                &meta(B256::ZERO, 1),
+                &event(),
+                &meta(HOST_TX, 2)
+            ),
+            Err(EnterTransactError::HostTxMismatch { enter: B256::ZERO, transact: HOST_TX })
+        );
+        assert_eq!(
+            TxTransact::from_enter_transact(&enter, &meta(HOST_TX, 2), &event(), &meta(HOST_TX, 2)),
+            Err(EnterTransactError::LogOrder { enter: 2, transact: 2 })
+        );
+        let mut other_rollup = enter;
+        other_rollup.rollupChainId = U256::from(1);
+        assert_eq!(
+            TxTransact::from_enter_transact(
+                &other_rollup,
+                &meta(HOST_TX, 1),
+                &event(),
+                &meta(HOST_TX, 2)
+            ),
+            Err(EnterTransactError::ChainIdMismatch {
+                enter: U256::from(1),
+                transact: U256::from(TEST_CHAIN_ID)
+            })
+        );
+    }
+}
@@ -0,0 +1,68 @@
+//! Shared helpers for unit tests.
+
+use crate::{HostContracts, Zenith::BlockHeader as ZenithHeader, ZenithTransaction};
+use alloy::consensus::{SignableTransaction, TxEip1559};
+use alloy::primitives::{b256, Address, Bytes, PrimitiveSignature, B256, U256};
+use alloy::signers::{local::PrivateKeySigner, SignerSync};
+
+pub(crate) const TEST_CHAIN_ID: u64 = 17;
+
+/// A header for a block on [`TEST_CHAIN_ID`].
+pub(crate) fn header() -> ZenithHeader {
+    ZenithHeader {
+        rollupChainId: U256::from(TEST_CHAIN_ID),
+        hostBlockNumber: U256::from(100),
+        gasLimit: U256::from(30_000_000),
+        rewardAddress: Address::repeat_byte(1),
+        blockDataHash: B256::ZERO,
+    }
+}
+
+/// An EIP-1559 transaction on [`TEST_CHAIN_ID`] with a fixed signature.
+pub(crate) fn tx(nonce: u64, input_len: usize) -> ZenithTransaction {
+    let sig = PrimitiveSignature::from_scalars_and_parity(
+        b256!("840cfc572845f5786e702984c2a582528cad4b49b2a10b9db1be7fca90058565"),
+        b256!("25e7109ceb98168d95b09b18bbf6b685130e0562f233877d492b94eee0c5b6d1"),
+        false,
+    );
+    TxEip1559 {
+        chain_id: TEST_CHAIN_ID,
+        nonce,
+        gas_limit: 21_000,
+        max_fee_per_gas: 4,
+        max_priority_fee_per_gas: 5,
+        to: Address::repeat_byte(6).into(),
+        value: U256::from(7),
+        access_list: Default::default(),
+        input: Bytes::from(vec![0xab; input_len]),
+    }
+    .into_signed(sig)
+    .into()
+}
+
+/// A signer with the private key `[i; 32]`.
+pub(crate) fn signer(i: u8) -> PrivateKeySigner {
+    PrivateKeySigner::from_slice(&[i; 32]).unwrap()
+}
+
+/// An EIP-1559 transaction on [`TEST_CHAIN_ID`] signed by `signer`.
+pub(crate) fn signed_tx(
+    signer: &PrivateKeySigner,
+    nonce: u64,
+    gas_limit: u64,
+) -> ZenithTransaction {
+    let tx = TxEip1559 { chain_id: TEST_CHAIN_ID, nonce, gas_limit, ..Default::default() };
+    let sig = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+    tx.into_signed(sig).into()
+}
+
+/// Host contracts at the addresses `0x0101..`, `0x0202..`, `0x0303..` and
+/// `0x0404..`.
+pub(crate) fn host_contracts() -> HostContracts {
+    HostContracts {
+        zenith: Address::repeat_byte(1),
+        passage: Address::repeat_byte(2),
+        transactor: Address::repeat_byte(3),
+        orders: Address::repeat_byte(4),
+    }
+}